@@ -0,0 +1,216 @@
+//! Benchmarks : the parse, part 1 & part 2 stages of a day are timed separately over several iterations.
+
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use common::{Result, Solver};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        };
+        // Padding is forwarded so that stages can be aligned in tables
+        f.pad(name)
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "part1" => Ok(Stage::Part1),
+            "part2" => Ok(Stage::Part2),
+            _ => Err(format!("unknown stage `{}`", s)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes the durations measured for a stage, there must be at least one
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// The stats of one stage of a day, as saved between runs to spot regressions
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Record {
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+fn time<T>(samples: &mut Vec<Duration>, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let res = black_box(f());
+    samples.push(start.elapsed());
+    res
+}
+
+/// Times every stage of a day `iterations` times, only the requested part (or both) is run
+pub fn bench<S: Solver>(
+    content: &str,
+    part: Option<u8>,
+    iterations: usize,
+) -> Result<Vec<(Stage, Stats)>> {
+    let mut parse = vec![];
+    let mut part1 = vec![];
+    let mut part2 = vec![];
+
+    for _ in 0..iterations.max(1) {
+        let input = time(&mut parse, || S::parse(black_box(content)))?;
+        if part.is_none() || part == Some(1) {
+            time(&mut part1, || S::part1(&input));
+        }
+        if part.is_none() || part == Some(2) {
+            time(&mut part2, || S::part2(&input));
+        }
+    }
+
+    Ok([
+        (Stage::Parse, parse),
+        (Stage::Part1, part1),
+        (Stage::Part2, part2),
+    ]
+    .into_iter()
+    .filter(|(_, samples)| !samples.is_empty())
+    .map(|(stage, samples)| (stage, Stats::new(samples)))
+    .collect())
+}
+
+const HEADER: &str = "day\tstage\tmin_ns\tmedian_ns\tmax_ns";
+
+/// Saves records as tab separated values, durations in nanoseconds
+pub fn save(records: &[Record], path: &Path) -> io::Result<()> {
+    let mut content = String::from(HEADER);
+    for record in records {
+        content.push_str(&format!(
+            "\n{}\t{}\t{}\t{}\t{}",
+            record.day,
+            record.stage,
+            record.stats.min.as_nanos(),
+            record.stats.median.as_nanos(),
+            record.stats.max.as_nanos()
+        ));
+    }
+    content.push('\n');
+    fs::write(path, content)
+}
+
+/// Loads records saved by `save`
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+    let invalid = |line: usize, why: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}:{}: {}", path.display(), line, why),
+        )
+    };
+    let nanos = |line: usize, value: &str| {
+        value
+            .parse::<u64>()
+            .map(Duration::from_nanos)
+            .map_err(|why| invalid(line, format!("invalid duration `{}`: {}", value, why)))
+    };
+
+    let content = fs::read_to_string(path)?;
+    let mut records = vec![];
+    for (i, line) in content.lines().enumerate().skip(1) {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 5 {
+            return Err(invalid(i + 1, format!("expected `{}`", HEADER)));
+        }
+        records.push(Record {
+            day: fields[0]
+                .parse()
+                .map_err(|why| invalid(i + 1, format!("invalid day: {}", why)))?,
+            stage: fields[1].parse().map_err(|why| invalid(i + 1, why))?,
+            stats: Stats {
+                min: nanos(i + 1, fields[2])?,
+                median: nanos(i + 1, fields[3])?,
+                max: nanos(i + 1, fields[4])?,
+            },
+        });
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::time::Duration;
+
+    use crate::bench::{load, save, Record, Stage, Stats};
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn it_can_summarize_samples() {
+        assert_eq!(
+            Stats::new(vec![ms(5), ms(1), ms(3)]),
+            Stats {
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            }
+        );
+        assert_eq!(Stats::new(vec![ms(4), ms(1), ms(2), ms(9)]).median, ms(3));
+    }
+
+    #[test]
+    fn it_can_save_and_load_records() {
+        let path = env::temp_dir().join(format!("aoc_bench_{}.tsv", std::process::id()));
+        let records = vec![
+            Record {
+                day: 7,
+                stage: Stage::Parse,
+                stats: Stats::new(vec![ms(1)]),
+            },
+            Record {
+                day: 7,
+                stage: Stage::Part2,
+                stats: Stats::new(vec![ms(120), ms(80), ms(100)]),
+            },
+        ];
+
+        save(&records, &path).unwrap();
+        assert_eq!(load(&path).unwrap(), records);
+        std::fs::remove_file(path).unwrap();
+    }
+}
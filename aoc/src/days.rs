@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+use common::{Answer, Result, Solver};
+
+use crate::bench::{self, Stage, Stats};
+
+/// The answer of a part & the time spent computing it, parsing excluded
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Parses the puzzle input then computes the requested part (or both)
+pub type SolveFn = fn(&str, Option<u8>) -> Result<Vec<Solution>>;
+
+/// Times the parse & requested part stages (or both parts) over the given number of iterations
+pub type BenchFn = fn(&str, Option<u8>, usize) -> Result<Vec<(Stage, Stats)>>;
+
+pub struct Day {
+    pub number: u8,
+    pub solve: SolveFn,
+    pub bench: BenchFn,
+}
+
+fn solve<S: Solver>(content: &str, part: Option<u8>) -> Result<Vec<Solution>> {
+    let input = S::parse(content)?;
+    let timed = |part: u8, solve_part: fn(&S::Input) -> Answer| {
+        let start = Instant::now();
+        let answer = solve_part(&input);
+        Solution {
+            part,
+            answer,
+            elapsed: start.elapsed(),
+        }
+    };
+
+    let mut solutions = vec![];
+    if part.is_none() || part == Some(1) {
+        solutions.push(timed(1, S::part1));
+    }
+    if part.is_none() || part == Some(2) {
+        solutions.push(timed(2, S::part2));
+    }
+    Ok(solutions)
+}
+
+fn day<S: Solver>() -> Day {
+    Day {
+        number: S::DAY,
+        solve: solve::<S>,
+        bench: bench::bench::<S>,
+    }
+}
+
+/// Every day of the calendar, ordered by day number
+pub fn all() -> Vec<Day> {
+    vec![
+        day::<day01::Day01>(),
+        day::<day02::Day02>(),
+        day::<day03::Day03>(),
+        day::<day04::Day04>(),
+        day::<day05::Day05>(),
+        day::<day06::Day06>(),
+        day::<day07::Day07>(),
+        day::<day08::Day08>(),
+        day::<day09::Day09>(),
+        day::<day10::Day10>(),
+        day::<day11::Day11>(),
+        day::<day12::Day12>(),
+        day::<day13::Day13>(),
+        day::<day14::Day14>(),
+    ]
+}
@@ -0,0 +1,96 @@
+//! Machine-readable output of the answers, as JSON.
+
+use std::fmt::Write;
+
+use common::Answer;
+
+use crate::days::Solution;
+
+/// Quotes & escapes a string as a JSON string
+pub fn string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Numbers stay numbers, text answers (such as day 13 drawing) become strings, unsolved & failed parts `null`
+pub fn answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(text) => string(text),
+        Answer::Unsolved | Answer::Failed(_) => "null".to_string(),
+    }
+}
+
+/// A `{day, part, answer, elapsed_ms}` record, with an `error` field after the `null` answer of a failed part
+pub fn record(day: u8, solution: &Solution) -> String {
+    let error = match &solution.answer {
+        Answer::Failed(why) => format!(", \"error\": {}", string(why)),
+        _ => String::new(),
+    };
+    format!(
+        "{{\"day\": {}, \"part\": {}, \"answer\": {}{}, \"elapsed_ms\": {:.3}}}",
+        day,
+        solution.part,
+        answer(&solution.answer),
+        error,
+        solution.elapsed.as_secs_f64() * 1000.0
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::Answer;
+
+    use crate::days::Solution;
+    use crate::json::{record, string};
+
+    #[test]
+    fn it_can_escape_strings() {
+        assert_eq!(string("#..#\n.##."), r##""#..#\n.##.""##);
+        assert_eq!(string("say \"hi\"\\"), r#""say \"hi\"\\""#);
+        assert_eq!(string("\u{1}"), r#""\u0001""#);
+    }
+
+    #[test]
+    fn it_can_format_records() {
+        let solution = |answer| Solution {
+            part: 2,
+            answer,
+            elapsed: Duration::from_micros(1500),
+        };
+
+        assert_eq!(
+            record(7, &solution(Answer::Number(168))),
+            r#"{"day": 7, "part": 2, "answer": 168, "elapsed_ms": 1.500}"#
+        );
+        assert_eq!(
+            record(13, &solution(Answer::Text("#.\n.#\n".to_string()))),
+            r##"{"day": 13, "part": 2, "answer": "#.\n.#\n", "elapsed_ms": 1.500}"##
+        );
+        assert_eq!(
+            record(14, &solution(Answer::Unsolved)),
+            r#"{"day": 14, "part": 2, "answer": null, "elapsed_ms": 1.500}"#
+        );
+        assert_eq!(
+            record(2, &solution(Answer::Failed("overflow".to_string()))),
+            r#"{"day": 2, "part": 2, "answer": null, "error": "overflow", "elapsed_ms": 1.500}"#
+        );
+    }
+}
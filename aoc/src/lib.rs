@@ -0,0 +1,6 @@
+//! The `aoc` runner : a registry of every day solver & the tooling built on top of it,
+//! shared by the `aoc` binary and the tests.
+
+pub mod bench;
+pub mod days;
+pub mod json;
@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+use std::env;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process;
+
+use aoc::bench::{self, Record, Stage};
+use aoc::{days, json};
+use common::input::{self, Input};
+use common::Error;
+
+const USAGE: &str = "Usage: aoc run <DAYS> [--part <1|2>] [--input <INPUT>] [--format <table|json>]
+       aoc bench <DAYS> [--part <1|2>] [--input <INPUT>] [--iterations <N>]
+                        [--save <FILE>] [--baseline <FILE>]
+
+  run           print the answers of the given days
+  bench         time the parse, part 1 & part 2 stages of the given days
+
+  DAYS          a single day (7), an inclusive range (3-9) or `all`
+  --part        only run the given part
+  --input       read the puzzle input from INPUT instead of dayNN/input.txt,
+                only allowed when a single day is run. INPUT is a path, `-` for
+                stdin, or a fixture of the day such as `test` or `test_small`
+  --format      how answers are printed : a `table` (default) or `json` records
+                of `{day, part, answer, elapsed_ms}`
+  --iterations  number of times each stage is timed, 10 by default
+  --save        save the benchmark results to FILE
+  --baseline    compare the medians to results previously saved in FILE";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run {
+        format: Format,
+    },
+    Bench {
+        iterations: usize,
+        save: Option<PathBuf>,
+        baseline: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    command: Command,
+    days: RangeInclusive<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let options = parse_args(&args).unwrap_or_else(|why| {
+        eprintln!("error: {}\n\n{}", why, USAGE);
+        process::exit(2)
+    });
+
+    let res = match &options.command {
+        Command::Run { format } => run(&options, *format),
+        Command::Bench {
+            iterations,
+            save,
+            baseline,
+        } => run_bench(&options, *iterations, save.as_ref(), baseline.as_ref()),
+    };
+
+    if let Err(why) = res {
+        eprintln!("error: {}", why);
+        process::exit(1);
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut iter = args.iter();
+
+    let command = match iter.next().map(String::as_str) {
+        Some("run") => Command::Run {
+            format: Format::Table,
+        },
+        Some("bench") => Command::Bench {
+            iterations: 10,
+            save: None,
+            baseline: None,
+        },
+        Some(command) => return Err(format!("unknown command `{}`", command)),
+        None => return Err("missing command".to_string()),
+    };
+
+    let days = match iter.next() {
+        Some(days) => parse_days(days)?,
+        None => return Err("missing days to run".to_string()),
+    };
+
+    let mut options = Options {
+        command,
+        days,
+        part: None,
+        input: None,
+    };
+
+    while let Some(arg) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("missing value for `{}`", arg))?;
+        match arg.as_str() {
+            "--part" => match value.as_str() {
+                "1" => options.part = Some(1),
+                "2" => options.part = Some(2),
+                _ => return Err(format!("invalid part `{}`, expected 1 or 2", value)),
+            },
+            "--input" => options.input = Some(value.clone()),
+            "--iterations" | "--save" | "--baseline" => match &mut options.command {
+                Command::Bench {
+                    iterations,
+                    save,
+                    baseline,
+                } => match arg.as_str() {
+                    "--iterations" => match value.parse::<usize>() {
+                        Ok(n) if n > 0 => *iterations = n,
+                        _ => return Err(format!("invalid number of iterations `{}`", value)),
+                    },
+                    "--save" => *save = Some(PathBuf::from(value)),
+                    _ => *baseline = Some(PathBuf::from(value)),
+                },
+                Command::Run { .. } => return Err(format!("`{}` is only allowed for bench", arg)),
+            },
+            "--format" => match &mut options.command {
+                Command::Run { format } => match value.as_str() {
+                    "table" => *format = Format::Table,
+                    "json" => *format = Format::Json,
+                    _ => {
+                        return Err(format!(
+                            "invalid format `{}`, expected table or json",
+                            value
+                        ))
+                    }
+                },
+                Command::Bench { .. } => {
+                    return Err("`--format` is only allowed for run".to_string())
+                }
+            },
+            _ => return Err(format!("unknown option `{}`", arg)),
+        }
+    }
+
+    if options.input.is_some() && options.days.start() != options.days.end() {
+        return Err("`--input` can only be used when running a single day".to_string());
+    }
+
+    Ok(options)
+}
+
+fn parse_days(arg: &str) -> Result<RangeInclusive<u8>, String> {
+    let last_day = days::all().len() as u8;
+    let parse_day = |day: &str| -> Result<u8, String> {
+        match day.trim().parse::<u8>() {
+            Ok(day) if (1..=last_day).contains(&day) => Ok(day),
+            _ => Err(format!(
+                "invalid day `{}`, expected a number between 1 and {}",
+                day, last_day
+            )),
+        }
+    };
+
+    if arg == "all" {
+        return Ok(1..=last_day);
+    }
+
+    match arg.split_once('-') {
+        Some((first, last)) => {
+            let (first, last) = (parse_day(first)?, parse_day(last)?);
+            if first > last {
+                return Err(format!(
+                    "invalid range `{}`, {} is after {}",
+                    arg, first, last
+                ));
+            }
+            Ok(first..=last)
+        }
+        None => {
+            let day = parse_day(arg)?;
+            Ok(day..=day)
+        }
+    }
+}
+
+fn run(options: &Options, format: Format) -> Result<(), String> {
+    if format == Format::Table {
+        println!(" Day | Part | Answer");
+        println!("-----+------+--------");
+    }
+
+    let mut records = vec![];
+    for day in days::all()
+        .into_iter()
+        .filter(|day| options.days.contains(&day.number))
+    {
+        let input = Input::resolve(options.input.as_deref(), &input::day_dir(day.number));
+        let content = input
+            .read()
+            .map_err(|why| format!("day {}: {}", day.number, why))?;
+
+        let solutions = (day.solve)(&content, options.part)
+            .map_err(|why| format!("day {}: {}", day.number, describe(why, &input, &content)))?;
+        for solution in solutions {
+            match format {
+                Format::Table => {
+                    print_answer(day.number, solution.part, &solution.answer.to_string())
+                }
+                Format::Json => records.push(json::record(day.number, &solution)),
+            }
+        }
+    }
+
+    // JSON is only printed once every day is solved, so that it is never left incomplete
+    if format == Format::Json {
+        println!("[\n  {}\n]", records.join(",\n  "));
+    }
+
+    Ok(())
+}
+
+fn run_bench(
+    options: &Options,
+    iterations: usize,
+    save: Option<&PathBuf>,
+    baseline: Option<&PathBuf>,
+) -> Result<(), String> {
+    let baseline: HashMap<(u8, Stage), Record> = match baseline {
+        Some(path) => bench::load(path)
+            .map_err(|why| format!("couldn't load baseline {}: {}", path.display(), why))?
+            .into_iter()
+            .map(|record| ((record.day, record.stage), record))
+            .collect(),
+        None => HashMap::new(),
+    };
+
+    println!(
+        " Day | Stage | {:>10} | {:>10} | {:>10} | vs baseline",
+        "Min", "Median", "Max"
+    );
+    println!("-----+-------+------------+------------+------------+------------");
+
+    let mut records = vec![];
+    for day in days::all()
+        .into_iter()
+        .filter(|day| options.days.contains(&day.number))
+    {
+        let input = Input::resolve(options.input.as_deref(), &input::day_dir(day.number));
+        let content = input
+            .read()
+            .map_err(|why| format!("day {}: {}", day.number, why))?;
+
+        let timings = (day.bench)(&content, options.part, iterations)
+            .map_err(|why| format!("day {}: {}", day.number, describe(why, &input, &content)))?;
+        for (stage, stats) in timings {
+            let change = match baseline.get(&(day.number, stage)) {
+                Some(previous) => {
+                    let previous = previous.stats.median.as_secs_f64();
+                    let current = stats.median.as_secs_f64();
+                    format!("{:+.1}%", (current - previous) / previous * 100.0)
+                }
+                None => "-".to_string(),
+            };
+            println!(
+                "{:>4} | {:<5} | {:>10} | {:>10} | {:>10} | {}",
+                day.number,
+                stage,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max),
+                change
+            );
+            records.push(Record {
+                day: day.number,
+                stage,
+                stats,
+            });
+        }
+    }
+
+    if let Some(path) = save {
+        bench::save(&records, path)
+            .map_err(|why| format!("couldn't save {}: {}", path.display(), why))?;
+    }
+
+    Ok(())
+}
+
+/// Parse errors are shown like compiler diagnostics, quoting the offending line of the input
+fn describe(why: Error, input: &Input, content: &str) -> String {
+    match why {
+        Error::Parse(why) => why.render(&input.to_string(), content),
+        why => why.to_string(),
+    }
+}
+
+fn print_answer(day: u8, part: u8, answer: &str) {
+    // Multi-line answers (such as day 13 drawing) are kept aligned in the answer column
+    for (i, line) in answer.trim_end().lines().enumerate() {
+        if i == 0 {
+            println!("{:>4} | {:>4} | {}", day, part, line);
+        } else {
+            println!("{:>4} | {:>4} | {}", "", "", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{parse_args, parse_days, Command, Format, Options};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split(' ').map(String::from).collect()
+    }
+
+    #[test]
+    fn it_can_parse_days() {
+        assert_eq!(parse_days("7"), Ok(7..=7));
+        assert_eq!(parse_days("3-9"), Ok(3..=9));
+        assert_eq!(parse_days("all"), Ok(1..=14));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("9-3").is_err());
+        assert!(parse_days("26").is_err());
+    }
+
+    #[test]
+    fn it_can_parse_a_run_command() {
+        assert_eq!(
+            parse_args(&args("run 7 --part 2 --input input_test.txt")),
+            Ok(Options {
+                command: Command::Run {
+                    format: Format::Table,
+                },
+                days: 7..=7,
+                part: Some(2),
+                input: Some("input_test.txt".to_string()),
+            })
+        );
+        assert!(parse_args(&args("run 1-3 --input input_test.txt")).is_err());
+        assert!(parse_args(&args("run 7 --part 3")).is_err());
+        assert!(parse_args(&args("run 7 --iterations 3")).is_err());
+        assert_eq!(
+            parse_args(&args("run all --format json")).map(|options| options.command),
+            Ok(Command::Run {
+                format: Format::Json
+            })
+        );
+        assert!(parse_args(&args("run all --format xml")).is_err());
+    }
+
+    #[test]
+    fn it_can_parse_a_bench_command() {
+        assert_eq!(
+            parse_args(&args("bench all --iterations 3 --save bench.tsv")),
+            Ok(Options {
+                command: Command::Bench {
+                    iterations: 3,
+                    save: Some(PathBuf::from("bench.tsv")),
+                    baseline: None,
+                },
+                days: 1..=14,
+                part: None,
+                input: None,
+            })
+        );
+        assert!(parse_args(&args("bench 7 --iterations 0")).is_err());
+    }
+}
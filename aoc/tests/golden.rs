@@ -0,0 +1,160 @@
+//! Golden answers suite : every `input*.txt` of a day is solved & compared to the answers
+//! recorded in the `answers.toml` manifest of the day.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use aoc::days;
+use common::{input, Answer};
+
+/// Expected answers by part, for one input file
+type Expected = BTreeMap<u8, String>;
+
+/// The answer of a part that is deliberately left out of the checks
+const UNCHECKED: &str = "unchecked";
+
+/// Reads the subset of TOML used by the manifests : `[input]` sections holding
+/// `partN = 123`, `partN = """multi-line"""` or `partN = "unchecked"` keys, and `#` comments.
+fn read_manifest(path: &Path) -> BTreeMap<String, Expected> {
+    let content = input::normalize(
+        &fs::read_to_string(path)
+            .unwrap_or_else(|why| panic!("couldn't read {}: {}", path.display(), why)),
+    );
+
+    let mut manifest: BTreeMap<String, Expected> = BTreeMap::new();
+    let mut section: Option<String> = None;
+    let mut lines = content.lines().enumerate();
+
+    while let Some((i, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            manifest.entry(name.to_string()).or_default();
+            section = Some(name.to_string());
+            continue;
+        }
+
+        let location = format!("{}:{}", path.display(), i + 1);
+        let (key, value) = line
+            .split_once('=')
+            .unwrap_or_else(|| panic!("{}: expected `partN = answer`", location));
+        let part = match key.trim() {
+            "part1" => 1,
+            "part2" => 2,
+            key => panic!("{}: unknown key `{}`", location, key),
+        };
+        let value = match value.trim() {
+            "\"\"\"" => {
+                let mut text = vec![];
+                for (_, line) in lines.by_ref() {
+                    if line.trim() == "\"\"\"" {
+                        break;
+                    }
+                    text.push(line);
+                }
+                text.join("\n")
+            }
+            value => value.trim_matches('"').to_string(),
+        };
+        let section = section
+            .as_ref()
+            .unwrap_or_else(|| panic!("{}: key outside of an input section", location));
+        manifest.get_mut(section).unwrap().insert(part, value);
+    }
+
+    manifest
+}
+
+fn check_day(day: u8) {
+    let dir = input::day_dir(day);
+    let manifest = read_manifest(&dir.join("answers.toml"));
+    let solver = days::all()
+        .into_iter()
+        .find(|d| d.number == day)
+        .unwrap_or_else(|| panic!("day {} isn't registered", day));
+
+    let mut fixtures: Vec<String> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("input") && name.ends_with(".txt"))
+        .map(|name| name.trim_end_matches(".txt").to_string())
+        .collect();
+    fixtures.sort();
+
+    assert_eq!(
+        fixtures,
+        manifest.keys().cloned().collect::<Vec<String>>(),
+        "day {}: every input file needs a section in answers.toml",
+        day
+    );
+
+    let mut failures = vec![];
+    for (fixture, expected) in &manifest {
+        let content = input::load(day, Some(&format!("{}.txt", fixture))).unwrap();
+        let answers = match (solver.solve)(&content, None) {
+            Ok(answers) => answers,
+            Err(why) => {
+                failures.push(format!("{}: {}", fixture, why));
+                continue;
+            }
+        };
+        for solution in answers {
+            let (part, answer) = (solution.part, solution.answer.to_string());
+            match expected.get(&part).map(String::as_str) {
+                None => failures.push(format!(
+                    "{} part {}: no answer recorded, write `part{} = \"unchecked\"` to skip it on purpose",
+                    fixture, part, part
+                )),
+                Some(UNCHECKED) => (),
+                Some(_) if matches!(solution.answer, Answer::Failed(_)) => {
+                    failures.push(format!("{} part {}: {}", fixture, part, answer))
+                }
+                Some(expected) if expected.trim_end() != answer.trim_end() => {
+                    failures.push(format!(
+                        "{} part {}: expected\n{}\nbut got\n{}",
+                        fixture, part, expected, answer
+                    ))
+                }
+                Some(_) => (),
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "day {} answers changed:\n{}",
+        day,
+        failures.join("\n")
+    );
+}
+
+macro_rules! golden {
+    ($($name:ident => $day:expr),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                check_day($day);
+            }
+        )*
+    };
+}
+
+golden! {
+    day01 => 1,
+    day02 => 2,
+    day03 => 3,
+    day04 => 4,
+    day05 => 5,
+    day06 => 6,
+    day07 => 7,
+    day08 => 8,
+    day09 => 9,
+    day10 => 10,
+    day11 => 11,
+    day12 => 12,
+    day13 => 13,
+    day14 => 14,
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+/// The answer of a puzzle part, as given to the Advent of Code website
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    /// Answers that are not a number, such as a drawing to read
+    Text(String),
+    /// The part has not been solved yet
+    Unsolved,
+    /// The part couldn't be solved from this input, & why : never an answer to compare
+    Failed(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(t) => write!(f, "{}", t),
+            Answer::Unsolved => write!(f, "not solved yet"),
+            Answer::Failed(why) => write!(f, "failed : {}", why),
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+/// Numbers beyond `i128` are kept as text rather than wrapped around
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Answer;
+
+    #[test]
+    fn it_keeps_numbers_too_large_for_an_i128() {
+        assert_eq!(Answer::from(42u128), Answer::Number(42));
+        assert_eq!(Answer::from(i128::MAX as u128), Answer::Number(i128::MAX));
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::Text("340282366920938463463374607431768211455".to_string())
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The puzzle input couldn't be read
+    Io { input: String, source: io::Error },
+    /// The puzzle input doesn't match what the day expects
+    Parse(ParseError),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { input, source } => write!(f, "couldn't read {}: {}", input, source),
+            Error::Parse(why) => write!(f, "invalid input: {}", why),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(why) => Some(why),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(why: ParseError) -> Self {
+        Error::Parse(why)
+    }
+}
+
+/// Where & why a puzzle input couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the input, starting at 1
+    pub line: usize,
+    /// Column of the offending text in its line, in characters & starting at 1
+    pub column: usize,
+    /// The offending text, empty when something is missing at the end of a line
+    pub text: String,
+    /// What the parser expected instead
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error on `text`, which must be a slice of the whole `content` being parsed : its line & column
+    /// are found from where it lies in `content`.
+    pub fn at(content: &str, text: &str, expected: impl Into<String>) -> Self {
+        let offset = (text.as_ptr() as usize).wrapping_sub(content.as_ptr() as usize);
+        assert!(
+            offset <= content.len(),
+            "`{}` is not a slice of the parsed content",
+            text
+        );
+
+        let before = &content[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Renders the error the way a compiler does : the message, where it is, then the offending line
+    /// of `content` with the text underlined. `origin` names where the content was read from.
+    pub fn render(&self, origin: &str, content: &str) -> String {
+        let source_line = content.split('\n').nth(self.line - 1).unwrap_or("");
+        self.render_line(origin, source_line)
+    }
+
+    /// Renders the error like `render`, from its line alone when the whole content isn't kept
+    pub fn render_line(&self, origin: &str, source_line: &str) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        // Text spanning several lines is only underlined up to the end of the first one
+        let width = self.text.split('\n').next().unwrap_or("").chars().count();
+
+        format!(
+            "{}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self.message(),
+            gutter,
+            origin,
+            self.line,
+            self.column,
+            gutter,
+            self.line,
+            source_line,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(width.max(1))
+        )
+    }
+
+    fn message(&self) -> String {
+        match self.text.as_str() {
+            "" => format!("expected {}, found end of line", self.expected),
+            text => format!("expected {}, found `{}`", self.expected, text),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line,
+            self.column,
+            self.message()
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,171 @@
+//! Loading of the puzzle inputs : from a path, from stdin, or from the fixtures stored next to each day.
+//!
+//! Every input is normalized once loaded, so that parsers only ever see `\n` separated lines
+//! without a trailing newline, whatever the editor that saved the file.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::{Error, Result};
+
+/// Where a puzzle input is read from
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    /// Resolves the input asked by the user :
+    /// - nothing means the `input.txt` of the day,
+    /// - `-` means stdin,
+    /// - an existing path is used as is,
+    /// - otherwise it is looked up in the day directory, either as a file name (`input_test.txt`)
+    ///   or as a fixture name (`test` for `input_test.txt`, `test_small` for `input_test_small.txt`).
+    pub fn resolve(arg: Option<&str>, day_dir: &Path) -> Self {
+        let arg = match arg {
+            None => return Input::File(day_dir.join("input.txt")),
+            Some("-") => return Input::Stdin,
+            Some(arg) => arg,
+        };
+
+        let candidates = [
+            PathBuf::from(arg),
+            day_dir.join(arg),
+            day_dir.join(format!("input_{}.txt", arg)),
+        ];
+        match candidates.iter().find(|path| path.is_file()) {
+            Some(path) => Input::File(path.clone()),
+            None => Input::File(PathBuf::from(arg)),
+        }
+    }
+
+    /// Reads the whole input, normalized
+    pub fn read(&self) -> Result<String> {
+        let content = match self {
+            Input::File(path) => fs::read_to_string(path),
+            Input::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content).map(|_| content)
+            }
+        };
+        content.map(|c| normalize(&c)).map_err(|source| Error::Io {
+            input: self.to_string(),
+            source,
+        })
+    }
+
+    /// Opens the input to be read line by line, for the days that stream inputs too large to hold in memory.
+    /// Nothing is normalized : the reader sees the lines as they are stored.
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            Input::File(path) => fs::File::open(path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|source| Error::Io {
+                    input: self.to_string(),
+                    source,
+                }),
+            Input::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::File(path) => write!(f, "{}", path.display()),
+            Input::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Strips a leading byte order mark, converts CRLF line endings to LF & removes trailing blank lines
+pub fn normalize(content: &str) -> String {
+    content
+        .strip_prefix('\u{feff}')
+        .unwrap_or(content)
+        .replace("\r\n", "\n")
+        .trim_end()
+        .to_string()
+}
+
+/// Directory of a day in the workspace, where its `input.txt` & fixtures live
+pub fn day_dir(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{:02}", day))
+}
+
+/// Reads the input of a day, see `Input::resolve` for what `arg` can be
+pub fn load(day: u8, arg: Option<&str>) -> Result<String> {
+    Input::resolve(arg, &day_dir(day)).read()
+}
+
+/// Reads the input of a day like `load`, for the binaries of the days : they exit when it can't be read
+pub fn load_or_exit(day: u8, arg: Option<&str>) -> String {
+    load(day, arg).unwrap_or_else(exit)
+}
+
+/// Prints `why` & exits, the way the binaries of the days give up on an error
+pub fn exit<T>(why: impl fmt::Display) -> T {
+    eprintln!("error: {}", why);
+    process::exit(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::input::{day_dir, load, normalize, Input};
+    use crate::Error;
+
+    #[test]
+    fn it_can_resolve_inputs() {
+        let dir = day_dir(12);
+
+        assert_eq!(
+            Input::resolve(None, &dir),
+            Input::File(dir.join("input.txt"))
+        );
+        assert_eq!(Input::resolve(Some("-"), &dir), Input::Stdin);
+        assert_eq!(
+            Input::resolve(Some("input_test_small.txt"), &dir),
+            Input::File(dir.join("input_test_small.txt"))
+        );
+        assert_eq!(
+            Input::resolve(Some("test_larger"), &dir),
+            Input::File(dir.join("input_test_larger.txt"))
+        );
+        assert_eq!(
+            Input::resolve(Some("missing.txt"), &dir),
+            Input::File(PathBuf::from("missing.txt"))
+        );
+    }
+
+    #[test]
+    fn it_can_normalize_inputs() {
+        assert_eq!(normalize("forward 5\ndown 5"), "forward 5\ndown 5");
+        assert_eq!(normalize("forward 5\ndown 5\n"), "forward 5\ndown 5");
+        assert_eq!(
+            normalize("forward 5\r\ndown 5\r\n\r\n"),
+            "forward 5\ndown 5"
+        );
+        assert_eq!(
+            normalize("\u{feff}forward 5\ndown 5\n \n"),
+            "forward 5\ndown 5"
+        );
+        // Blank lines separating sections are kept
+        assert_eq!(normalize("NNCB\r\n\r\nCH -> B\r\n"), "NNCB\n\nCH -> B");
+    }
+
+    #[test]
+    fn it_reports_unreadable_inputs() {
+        match load(12, Some("missing.txt")) {
+            Err(Error::Io { input, .. }) => assert_eq!(input, "missing.txt"),
+            other => panic!("expected an I/O error, got {:?}", other),
+        }
+    }
+}
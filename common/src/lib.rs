@@ -0,0 +1,11 @@
+//! Shared building blocks of every day : the `Solver` trait, the types it works with and input loading.
+
+mod answer;
+mod error;
+pub mod input;
+pub mod parse;
+mod solver;
+
+pub use answer::Answer;
+pub use error::{Error, ParseError, Result};
+pub use solver::Solver;
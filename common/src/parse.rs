@@ -0,0 +1,94 @@
+//! Helpers shared by the day parsers, so that a malformed input is reported with the line, column
+//! & text at fault rather than with a panic.
+//!
+//! Every text given to these helpers must be a slice of the whole `content` being parsed, which is
+//! what `split`, `trim` & co. return : that is how the error finds its position.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ParseError, Result};
+
+/// Error on `text` of `content`, which isn't what was `expected`
+pub fn error(content: &str, text: &str, expected: impl Into<String>) -> crate::Error {
+    ParseError::at(content, text, expected).into()
+}
+
+/// Parses `text` of `content` as a number
+pub fn number<T>(content: &str, text: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse()
+        .map_err(|why| error(content, text, format!("a number ({})", why)))
+}
+
+/// Unwraps a field of `line` that may be missing, such as the next item of a `split`.
+/// A missing field is reported at the end of the line.
+pub fn field<'a>(
+    content: &str,
+    line: &'a str,
+    field: Option<&'a str>,
+    expected: &str,
+) -> Result<&'a str> {
+    field.ok_or_else(|| error(content, &line[line.len()..], expected))
+}
+
+/// Asserts that `result` is a parse error at `(line, column)`, for the tests of the day parsers
+#[track_caller]
+pub fn assert_parse_error_at<T: fmt::Debug>(result: Result<T>, (line, column): (usize, usize)) {
+    match result {
+        Err(crate::Error::Parse(why)) => {
+            assert_eq!((why.line, why.column), (line, column), "{}", why)
+        }
+        other => panic!(
+            "expected a parse error at {}:{}, got {:?}",
+            line, column, other
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{field, number};
+    use crate::{Error, ParseError};
+
+    const CONTENT: &str = "forward 5\ndown é5\nup";
+
+    #[test]
+    fn it_locates_errors_in_the_content() {
+        let line = CONTENT.split('\n').nth(1).unwrap();
+        let unit = line.split(' ').nth(1).unwrap();
+        match number::<u32>(CONTENT, unit) {
+            Err(Error::Parse(why)) => {
+                assert_eq!((why.line, why.column), (2, 6));
+                assert_eq!(why.text, "é5");
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+
+        let line = CONTENT.split('\n').nth(2).unwrap();
+        match field(CONTENT, line, line.split(' ').nth(1), "a number of units") {
+            Err(Error::Parse(why)) => {
+                assert_eq!((why.line, why.column), (3, 3));
+                assert_eq!(why.text, "");
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_renders_errors_like_a_compiler() {
+        let line = CONTENT.split('\n').nth(1).unwrap();
+        let why = ParseError::at(CONTENT, &line[..4], "one of `forward`, `down` or `up`");
+        assert_eq!(
+            why.render("day02/input.txt", CONTENT),
+            "expected one of `forward`, `down` or `up`, found `down`
+ --> day02/input.txt:2:1
+  |
+2 | down é5
+  | ^^^^"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{Answer, Result};
+
+/// A day of the calendar, split in separate stages so that every day can be run the same way :
+/// the input is parsed once, then both parts are computed from it.
+pub trait Solver {
+    /// Day number in the calendar
+    const DAY: u8;
+
+    /// What the puzzle input is parsed into
+    type Input;
+
+    fn parse(content: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(input: &Self::Input) -> Answer;
+}
@@ -0,0 +1,169 @@
+use common::{parse, Answer, Result, Solver};
+
+mod sonar;
+
+pub use sonar::{analyze, AnalysisError, BadReadings, Mode, Report, Sonar, MAX_REPORTED};
+
+/// A sonar reading : the depth of the sea floor
+pub type Measurement = u64;
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<Measurement>;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_measurements(content)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_larger_measurements(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        count_larger_measurements_in_windows(input, 3).into()
+    }
+}
+
+/// Parses one reading per line, failing on the first line that isn't one
+pub fn parse_measurements(content: &str) -> Result<Vec<Measurement>> {
+    content
+        .split('\n')
+        .map(|line| parse::number(content, line))
+        .collect()
+}
+
+/// Reads one line of a sweep whatever its line ending, `None` when it isn't a reading
+pub fn parse_measurement(line: &str) -> Option<Measurement> {
+    line.trim_start_matches('\u{feff}').trim().parse().ok()
+}
+
+/// How many sums of `window` consecutive measurements are larger than the previous sum
+pub fn count_larger_measurements_in_windows(measurements: &[Measurement], window: usize) -> u64 {
+    let mut sonar = Sonar::new(window);
+    for depth in measurements {
+        sonar.push(*depth);
+    }
+    sonar.report().increases
+}
+
+pub fn count_larger_measurements(measurements: &[Measurement]) -> u64 {
+    count_larger_measurements_in_windows(measurements, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        analyze, count_larger_measurements_in_windows, parse_measurements, AnalysisError, Mode,
+        Sonar, MAX_REPORTED,
+    };
+
+    const CONTENT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+
+    #[test]
+    fn it_can_count_increases_in_any_window() {
+        let measurements = parse_measurements(CONTENT).unwrap();
+        assert_eq!(count_larger_measurements_in_windows(&measurements, 1), 7);
+        assert_eq!(count_larger_measurements_in_windows(&measurements, 3), 5);
+        assert_eq!(count_larger_measurements_in_windows(&measurements, 10), 0);
+        assert_eq!(count_larger_measurements_in_windows(&measurements, 11), 0);
+
+        let mut sonar = Sonar::new(3);
+        assert_eq!(sonar.push(199), None);
+        assert_eq!(sonar.push(200), None);
+        assert_eq!(sonar.push(208), Some(607.0 / 3.0));
+        assert_eq!(sonar.push(210), Some(206.0));
+    }
+
+    #[test]
+    fn it_can_analyze_a_stream() {
+        let stream = format!("{}\r\noops\n{}\n", CONTENT, u64::MAX);
+        let reports = analyze(stream.as_bytes(), &[1, 3], Mode::Lenient).unwrap();
+
+        assert_eq!(reports[0].readings, 11);
+        assert_eq!((reports[0].skipped, reports[0].bridged_windows), (1, 0));
+        assert_eq!((reports[1].skipped, reports[1].bridged_windows), (1, 1));
+        assert_eq!(reports[0].increases, 8);
+        assert_eq!(reports[0].longest_run, 3);
+        assert_eq!(reports[0].largest_jump, u64::MAX as i128 - 263);
+        assert_eq!(reports[1].increases, 6);
+        assert_eq!(reports[1].longest_run, 5);
+        assert_eq!(reports[1].lowest_average, Some(607.0 / 3.0));
+        assert_eq!(
+            reports[1].highest_average,
+            Some((u64::MAX as u128 + 523) as f64 / 3.0)
+        );
+    }
+
+    #[test]
+    fn it_reports_bad_readings() {
+        assert!(parse_measurements("199\n2OO").is_err());
+
+        let stream = format!("{}\n  oops\n1\nx2", CONTENT);
+        let why = analyze(stream.as_bytes(), &[3], Mode::Strict).unwrap_err();
+        let AnalysisError::BadReadings(why) = why else {
+            panic!("expected bad readings, got {:?}", why)
+        };
+        assert_eq!(why.count, 2);
+        let found: Vec<_> = why
+            .errors
+            .iter()
+            .map(|why| (why.line, why.column, why.text.as_str()))
+            .collect();
+        assert_eq!(found, [(11, 3, "oops"), (13, 1, "x2")]);
+        assert_eq!(
+            why.diagnostics("sweep.txt")[0],
+            "expected a depth reading, found `oops`
+  --> sweep.txt:11:3
+   |
+11 |   oops
+   |   ^^^^"
+        );
+
+        // Blank lines only count as readings when more readings follow them
+        let stream = "1\n\n\n2\n3\n\n\n";
+        let why = analyze(stream.as_bytes(), &[1], Mode::Strict).unwrap_err();
+        let AnalysisError::BadReadings(why) = why else {
+            panic!("expected bad readings, got {:?}", why)
+        };
+        let found: Vec<_> = why
+            .errors
+            .iter()
+            .map(|why| (why.line, why.text.as_str()))
+            .collect();
+        assert_eq!(found, [(2, ""), (3, "")]);
+
+        // Only the first ones are kept in the error
+        let garbage = "x\n".repeat(MAX_REPORTED + 5);
+        let why = analyze(garbage.as_bytes(), &[1], Mode::Strict).unwrap_err();
+        assert!(why.to_string().ends_with(
+            "& 5 more (first one : line 1, column 1: expected a depth reading, found `x`)"
+        ));
+
+        match analyze("1\nx\n2".as_bytes(), &[1], Mode::Strict) {
+            Err(why) => assert_eq!(
+                why.to_string(),
+                "1 line isn't a depth reading, on line 2 \
+                 (first one : line 2, column 1: expected a depth reading, found `x`)"
+            ),
+            other => panic!("expected bad readings, got {:?}", other),
+        }
+
+        // A corrupted line is a bad reading, not a failure to read the sweep
+        let corrupted = b"1\n\xff\xfe\n2\n3";
+        let reports = analyze(&corrupted[..], &[1], Mode::Lenient).unwrap();
+        assert_eq!((reports[0].readings, reports[0].skipped), (3, 1));
+        match analyze(&corrupted[..], &[1], Mode::Strict) {
+            Err(AnalysisError::BadReadings(why)) => assert_eq!(why.errors[0].line, 2),
+            other => panic!("expected bad readings, got {:?}", other),
+        }
+
+        let reports = analyze(stream.as_bytes(), &[2], Mode::Lenient).unwrap();
+        assert_eq!(reports[0].readings, 3);
+        assert_eq!(reports[0].skipped, 2);
+        assert_eq!(reports[0].bridged_windows, 1);
+        assert_eq!(reports[0].increases, 1);
+    }
+}
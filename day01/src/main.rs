@@ -1,71 +1,83 @@
-#![feature(array_windows)]
+use std::env;
 
-use std::{fs::File, path::Path, io::Read};
+use common::input::{self, Input};
+use common::{Error, Solver};
+use day01::{analyze, AnalysisError, Day01, Mode};
 
+/// Streams the sweep rather than loading it, so that it can be of any size.
+/// Arguments are `[INPUT] [WINDOW] [--lenient]` : the windows hold 3 readings by default, and the lines that
+/// aren't a reading fail the analysis unless it is lenient.
 fn main() {
-    // Create a path to the desired file
-    let path = Path::new("input.txt");
-    let display = path.display();
-
-    // Open the path in read-only mode, returns `io::Result<File>`
-    let mut file = match File::open(&path) {
-        Err(why) => panic!("couldn't open {}: {}", display, why),
-        Ok(file) => file,
+    let (flags, args): (Vec<String>, Vec<String>) =
+        env::args().skip(1).partition(|arg| arg.starts_with("--"));
+    let mode = match flags.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => Mode::Strict,
+        ["--lenient"] => Mode::Lenient,
+        _ => input::exit(format!(
+            "unknown options {:?}, only `--lenient` is supported",
+            flags
+        )),
     };
 
-    // Read the file contents into a string, returns `io::Result<usize>`
-    let mut content = String::new();
-    match file.read_to_string(&mut content) {
-        Err(why) => panic!("couldn't read {}: {}", display, why),
-        Ok(_) => print!("{} contains:\n{}", display, content),
-    }
-
-    count_larger_measurements(&content);
-    
-    count_larger_measurements_in_windows(&content);
-    
-}
-
-fn count_larger_measurements_in_windows(content: &String) {
-    let mut previous_total: u16 = u16::MAX;
-    let mut increase_count: u16 = 0;
+    let input = Input::resolve(
+        args.first().map(String::as_str),
+        &input::day_dir(Day01::DAY),
+    );
+    let window = match args.get(1) {
+        None => 3,
+        Some(arg) => arg.parse().ok().filter(|w| *w > 0).unwrap_or_else(|| {
+            input::exit(format!(
+                "the window size must be a positive number, not `{}`",
+                arg
+            ))
+        }),
+    };
 
-    let ar: Vec<&str> = content.split('\n').collect();
-    let mut total: u16;
-    for window in ar.array_windows::<3>() {
-        total = 0;
-        for x in *window {
-            match x.parse::<u16>() {
-                Ok(x) => {
-                    total+=x;
-                },
-                Err(_) => ()
-            }
+    let reader = input.open().unwrap_or_else(input::exit);
+    let reports = match analyze(reader, &[1, window], mode) {
+        Ok(reports) => reports,
+        Err(AnalysisError::Io(source)) => {
+            let why = Error::Io {
+                input: input.to_string(),
+                source,
+            };
+            input::exit(why)
         }
-        if total > previous_total {
-            increase_count+=1;
+        Err(AnalysisError::BadReadings(why)) => {
+            for diagnostic in why.diagnostics(&input.to_string()) {
+                eprintln!("error: {}\n", diagnostic);
+            }
+            input::exit(why)
         }
-        previous_total = total;
+    };
+    let (single, windowed) = (reports[0], reports[1]);
+
+    if windowed.skipped > 0 {
+        println!(
+            "Skipped {} lines that aren't a reading, {} windows of {} span them.",
+            windowed.skipped, windowed.bridged_windows, window
+        );
     }
 
-    println!("There are {} measurements that are larger than the previous measurement using windows.", increase_count);
-}
+    println!(
+        "There are {} measurements that are larger than the previous measurement.",
+        single.increases
+    );
 
-fn count_larger_measurements(content: &String) {
-    let mut previous_measurement: u16 = u16::MAX;
-    let mut increase_count: u16 = 0;
+    println!(
+        "There are {} measurements that are larger than the previous measurement using windows of {}.",
+        windowed.increases, window
+    );
 
-    content.split('\n').for_each({
-        |x| match x.parse::<u16>() {
-            Ok(x) => {
-                if x > previous_measurement {
-                    increase_count+=1;
-                }
-                previous_measurement = x;
-            },
-            Err(_) => ()
-        }
-    });
+    println!(
+        "Out of {} readings, the longest run is {} increases in a row and the largest jump is {}.",
+        windowed.readings, windowed.longest_run, windowed.largest_jump
+    );
 
-    println!("There are {} measurements that are larger than the previous measurement.", increase_count);
+    if let (Some(lowest), Some(highest)) = (windowed.lowest_average, windowed.highest_average) {
+        println!(
+            "The moving average goes from {:.2} to {:.2}.",
+            lowest, highest
+        );
+    }
 }
@@ -0,0 +1,295 @@
+//! Streaming analysis of a sonar sweep, in constant memory whatever its length.
+//!
+//! Two consecutive windows share all their readings but one : a window sum is larger than the previous one
+//! exactly when the reading entering the window is larger than the one leaving it. Only the readings of the
+//! current window are kept, never the sweep.
+//!
+//! A line that isn't a reading, be it only because it isn't valid UTF-8, either fails a `Strict` analysis, or
+//! is skipped by a `Lenient` one : the readings around it then form the windows, as if it weren't there.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use common::ParseError;
+
+use crate::parse_measurement;
+
+/// How an analysis handles the lines that aren't a reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// They fail the analysis, once the whole sweep has been read to find them all
+    Strict,
+    /// They are skipped & counted in the report
+    Lenient,
+}
+
+/// How many bad readings a `BadReadings` error keeps the details of, so that it stays small whatever the sweep
+pub const MAX_REPORTED: usize = 100;
+
+/// Why an analysis failed
+#[derive(Debug)]
+pub enum AnalysisError {
+    /// The sweep couldn't be read
+    Io(io::Error),
+    /// Lines of the sweep aren't a reading, in a `Strict` analysis
+    BadReadings(BadReadings),
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalysisError::Io(why) => write!(f, "{}", why),
+            AnalysisError::BadReadings(why) => write!(f, "{}", why),
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnalysisError::Io(why) => Some(why),
+            AnalysisError::BadReadings(why) => Some(why),
+        }
+    }
+}
+
+impl From<io::Error> for AnalysisError {
+    fn from(why: io::Error) -> Self {
+        AnalysisError::Io(why)
+    }
+}
+
+/// The lines of a sweep that aren't a reading, which fail a `Strict` analysis
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BadReadings {
+    /// The first `MAX_REPORTED` of them, in the order of the sweep
+    pub errors: Vec<ParseError>,
+    /// How many there are in all
+    pub count: u64,
+    /// The lines of `errors`, as the sweep isn't kept to quote them from
+    lines: Vec<String>,
+}
+
+impl BadReadings {
+    fn push(&mut self, why: ParseError, line: &str) {
+        self.count += 1;
+        if self.errors.len() < MAX_REPORTED {
+            self.errors.push(why);
+            self.lines.push(line.to_string());
+        }
+    }
+
+    /// Every kept error rendered like a compiler diagnostic quoting its line, then how many more there are.
+    /// `origin` names where the sweep was read from.
+    pub fn diagnostics(&self, origin: &str) -> Vec<String> {
+        let mut diagnostics: Vec<String> = self
+            .errors
+            .iter()
+            .zip(&self.lines)
+            .map(|(why, line)| why.render_line(origin, line))
+            .collect();
+        let more = self.count - self.errors.len() as u64;
+        if more > 0 {
+            diagnostics.push(format!("{} more lines aren't a depth reading", more));
+        }
+        diagnostics
+    }
+}
+
+impl fmt::Display for BadReadings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.errors.iter().map(|why| why.line.to_string()).collect();
+        if self.count == 1 {
+            write!(
+                f,
+                "1 line isn't a depth reading, on line {}",
+                lines.join(", ")
+            )?;
+        } else {
+            write!(
+                f,
+                "{} lines aren't a depth reading, on lines {}",
+                self.count,
+                lines.join(", ")
+            )?;
+        }
+        if self.count > self.errors.len() as u64 {
+            write!(f, " & {} more", self.count - self.errors.len() as u64)?;
+        }
+        if let Some(first) = self.errors.first() {
+            write!(f, " (first one : {})", first)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BadReadings {}
+
+/// What a sonar sweep looks like through windows of a given size
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Report {
+    /// How many readings were analyzed, unreadable lines excluded
+    pub readings: u64,
+    /// How many lines were skipped as they weren't a reading
+    pub skipped: u64,
+    /// How many windows hold readings from both sides of a skipped line
+    pub bridged_windows: u64,
+    /// How many window sums are larger than the previous one
+    pub increases: u64,
+    /// The most window sums in a row that are each larger than the previous one
+    pub longest_run: u64,
+    /// The largest change between two consecutive window sums, negative when the depth decreases
+    pub largest_jump: i128,
+    /// The lowest average depth of a window, `None` until the first window is full
+    pub lowest_average: Option<f64>,
+    /// The highest average depth of a window, `None` until the first window is full
+    pub highest_average: Option<f64>,
+}
+
+/// Analyzes the readings of a sweep one at a time through windows of `window` readings
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sonar {
+    window: usize,
+    /// The readings of the current window, oldest first
+    recent: VecDeque<u64>,
+    sum: u128,
+    /// How many window sums in a row have increased so far
+    run: u64,
+    /// How many readings there were before the last skipped line
+    gap: Option<u64>,
+    report: Report,
+}
+
+impl Sonar {
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "a window holds at least one reading");
+        Sonar {
+            window,
+            recent: VecDeque::new(),
+            sum: 0,
+            run: 0,
+            gap: None,
+            report: Report::default(),
+        }
+    }
+
+    /// Adds the next reading & returns the moving average of the window it completes,
+    /// `None` while the first window fills up
+    pub fn push(&mut self, depth: u64) -> Option<f64> {
+        self.report.readings += 1;
+        self.recent.push_back(depth);
+        self.sum += depth as u128;
+
+        if self.recent.len() < self.window {
+            return None;
+        }
+        if self.recent.len() > self.window {
+            let leaving = self.recent.pop_front().unwrap();
+            self.sum -= leaving as u128;
+
+            let jump = depth as i128 - leaving as i128;
+            if jump > 0 {
+                self.report.increases += 1;
+                self.run += 1;
+                self.report.longest_run = self.report.longest_run.max(self.run);
+            } else {
+                self.run = 0;
+            }
+            if jump.abs() > self.report.largest_jump.abs() {
+                self.report.largest_jump = jump;
+            }
+        }
+
+        if let Some(gap) = self.gap {
+            // The window holds the readings after the first `readings - window` ones
+            if gap > 0 && gap + (self.window as u64) > self.report.readings {
+                self.report.bridged_windows += 1;
+            }
+        }
+
+        let average = self.sum as f64 / self.window as f64;
+        let report = &mut self.report;
+        report.lowest_average = Some(report.lowest_average.map_or(average, |a| a.min(average)));
+        report.highest_average = Some(report.highest_average.map_or(average, |a| a.max(average)));
+        Some(average)
+    }
+
+    /// Skips a line that isn't a reading : the windows close over it
+    pub fn skip(&mut self) {
+        self.report.skipped += 1;
+        self.gap = Some(self.report.readings);
+    }
+
+    pub fn report(&self) -> Report {
+        self.report
+    }
+}
+
+/// Analyzes the sweep of `reader`, one reading per line, through windows of each size of `windows` in a
+/// single pass. Blank lines at the end of the sweep are ignored, any other line that isn't a reading is
+/// handled according to `mode` : a `Strict` analysis fails with the `BadReadings` of the sweep.
+pub fn analyze(
+    mut reader: impl BufRead,
+    windows: &[usize],
+    mode: Mode,
+) -> Result<Vec<Report>, AnalysisError> {
+    let mut sonars: Vec<Sonar> = windows.iter().map(|window| Sonar::new(*window)).collect();
+    let mut bad_readings = BadReadings::default();
+    // Blank lines are only known to be bad readings once a reading follows them
+    let mut blank_lines: Option<(usize, u64)> = None;
+
+    let mut bytes = vec![];
+    let mut number = 0;
+    while reader.read_until(b'\n', &mut bytes)? > 0 {
+        number += 1;
+        // A corrupted byte can't be part of a reading : the line is then only a bad one, replacement
+        // characters standing for its bytes in the report
+        let line = String::from_utf8_lossy(&bytes);
+        let unmarked = line.trim_start_matches('\u{feff}');
+        let text = unmarked.trim();
+        if text.is_empty() {
+            let (first, count) = blank_lines.unwrap_or((number, 0));
+            blank_lines = Some((first, count + 1));
+            bytes.clear();
+            continue;
+        }
+
+        if let Some((first, count)) = blank_lines.take() {
+            match mode {
+                Mode::Strict => (first..first + count as usize)
+                    .for_each(|blank| bad_readings.push(invalid_reading(blank, "", 0), "")),
+                Mode::Lenient => (0..count).for_each(|_| sonars.iter_mut().for_each(Sonar::skip)),
+            }
+        }
+        match parse_measurement(text) {
+            Some(depth) => {
+                for sonar in sonars.iter_mut() {
+                    sonar.push(depth);
+                }
+            }
+            None if mode == Mode::Strict => {
+                let indent = unmarked[..unmarked.len() - unmarked.trim_start().len()].chars();
+                let source_line = unmarked.trim_end_matches(['\r', '\n']);
+                bad_readings.push(invalid_reading(number, text, indent.count()), source_line)
+            }
+            None => sonars.iter_mut().for_each(Sonar::skip),
+        }
+        bytes.clear();
+    }
+
+    if bad_readings.count > 0 {
+        return Err(AnalysisError::BadReadings(bad_readings));
+    }
+    Ok(sonars.iter().map(Sonar::report).collect())
+}
+
+/// The error on `text`, found after `indent` characters of the line of the sweep at `number`
+fn invalid_reading(number: usize, text: &str, indent: usize) -> ParseError {
+    ParseError {
+        line: number,
+        column: indent + 1,
+        text: text.to_string(),
+        expected: "a depth reading".to_string(),
+    }
+}
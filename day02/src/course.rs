@@ -0,0 +1,158 @@
+//! The course language, for the puzzle inputs as well as hand written courses :
+//! - `forward 5`, `back 5`, `down 5` & `up 5` move the submarine,
+//! - `surface` brings it back to the surface,
+//! - `repeat 3 { forward 2 down 1 }` repeats the commands of the block, blocks can span several lines
+//!   & be nested,
+//! - `#` starts a comment, up to the end of the line.
+//!
+//! Repeat blocks are unrolled while parsing : a course is the list of the moves it makes.
+
+use std::iter::Peekable;
+
+use common::{parse, Result};
+
+use crate::{Move, MoveKind};
+
+/// Upper bound of the moves of an unrolled course, so that a typo in a repeat count can't exhaust memory
+pub const MAX_MOVES: usize = 10_000_000;
+
+/// A word of the course, as a slice of the parsed content
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    /// The line the word is on, starting at 1
+    line: usize,
+    /// That line, without its comment
+    code: &'a str,
+}
+
+pub fn parse_moves(content: &str) -> Result<Vec<Move>> {
+    let mut tokens = tokenize(content).into_iter().peekable();
+    let moves = parse_block(content, &mut tokens, None)?;
+    match tokens.next() {
+        Some(token) => Err(parse::error(content, token.text, "a command")),
+        None => Ok(moves),
+    }
+}
+
+/// Splits `content` into words & braces, without the comments
+fn tokenize(content: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    for (i, line) in content.split('\n').enumerate() {
+        let code = line.split('#').next().unwrap_or("");
+        let token = |text| Token {
+            text,
+            line: i + 1,
+            code,
+        };
+
+        let mut start = None;
+        for (j, c) in code.char_indices() {
+            if !c.is_whitespace() && c != '{' && c != '}' {
+                start = start.or(Some(j));
+                continue;
+            }
+            if let Some(s) = start.take() {
+                tokens.push(token(&code[s..j]));
+            }
+            if c == '{' || c == '}' {
+                tokens.push(token(&code[j..j + 1]));
+            }
+        }
+        if let Some(s) = start {
+            tokens.push(token(&code[s..]));
+        }
+    }
+    tokens
+}
+
+/// Parses commands up to the `}` closing the block opened by `opening`, or up to the end of the content
+/// for the whole course
+fn parse_block<'a>(
+    content: &str,
+    tokens: &mut Peekable<impl Iterator<Item = Token<'a>>>,
+    opening: Option<&'a str>,
+) -> Result<Vec<Move>> {
+    let mut moves = vec![];
+    loop {
+        let Some(token) = tokens.next() else {
+            return match opening {
+                Some(brace) => Err(parse::error(content, brace, "a block closed by a `}`")),
+                None => Ok(moves),
+            };
+        };
+
+        let kind = match token.text {
+            "}" if opening.is_some() => return Ok(moves),
+            "surface" => {
+                moves.push(Move::new(MoveKind::Surface, 0, token.line));
+                continue;
+            }
+            "repeat" => {
+                let count = unit(content, tokens, token, "a repeat count")?;
+                let brace = match tokens.next() {
+                    Some(brace) if brace.text == "{" => brace.text,
+                    Some(other) => return Err(parse::error(content, other.text, "`{`")),
+                    None => return Err(end_error(content, "`{` after the repeat count")),
+                };
+                let block = parse_block(content, tokens, Some(brace))?;
+                if block.len().saturating_mul(count as usize) + moves.len() > MAX_MOVES {
+                    return Err(parse::error(
+                        content,
+                        token.text,
+                        format!("a course of at most {} moves once unrolled", MAX_MOVES),
+                    ));
+                }
+                for _ in 0..count {
+                    moves.extend_from_slice(&block);
+                }
+                continue;
+            }
+            "forward" => MoveKind::Forward,
+            "back" => MoveKind::Back,
+            "down" => MoveKind::Down,
+            "up" => MoveKind::Up,
+            _ => {
+                return Err(parse::error(
+                    content,
+                    token.text,
+                    "one of `forward`, `back`, `down`, `up`, `surface` or `repeat`",
+                ))
+            }
+        };
+        let unit = unit(content, tokens, token, "a number of units")?;
+        moves.push(Move::new(kind, unit, token.line));
+        if moves.len() > MAX_MOVES {
+            return Err(parse::error(
+                content,
+                token.text,
+                format!("a course of at most {} moves", MAX_MOVES),
+            ));
+        }
+    }
+}
+
+/// Parses the number following `command`, which must be on the same line
+fn unit<'a>(
+    content: &str,
+    tokens: &mut Peekable<impl Iterator<Item = Token<'a>>>,
+    command: Token<'a>,
+    expected: &str,
+) -> Result<u32> {
+    match tokens.peek() {
+        Some(unit) if unit.line == command.line && unit.text != "{" && unit.text != "}" => {
+            let unit = unit.text;
+            tokens.next();
+            parse::number(content, unit)
+        }
+        _ => {
+            let code = command.code.trim_end();
+            Err(parse::error(content, &code[code.len()..], expected))
+        }
+    }
+}
+
+/// Error on something missing at the very end of `content`
+fn end_error(content: &str, expected: &str) -> common::Error {
+    parse::error(content, &content[content.len()..], expected)
+}
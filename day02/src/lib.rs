@@ -0,0 +1,334 @@
+use std::fmt;
+
+use common::{Answer, Result, Solver};
+
+mod course;
+mod navigation;
+mod planner;
+mod trajectory;
+
+pub use course::{parse_moves, MAX_MOVES};
+pub use navigation::{navigate, trace, Aimed, Current, Direct, Navigation, NavigationModel};
+pub use planner::{plan, Plan};
+pub use trajectory::{depth_profiles, Trajectory};
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Move>;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_moves(content)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        answer(navigate(input, &Direct))
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        answer(navigate(input, &Aimed))
+    }
+}
+
+/// The answer where a course ends, or why it has no end when it overflows
+fn answer(end: std::result::Result<Position, Overflow>) -> Answer {
+    match end {
+        Ok(position) => position.answer().into(),
+        Err(overflow) => Answer::Failed(overflow.to_string()),
+    }
+}
+
+/// The state of the submarine : the depth grows downwards, it is negative above the surface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    pub aim: i64,
+    pub depth: i64,
+    pub hpos: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    kind: MoveKind,
+    unit: u32,
+    /// The line of the course the move comes from, starting at 1
+    line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveKind {
+    Forward,
+    Back,
+    Down,
+    Up,
+    Surface,
+}
+
+/// A move that would take the submarine state beyond what an `i64` holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    /// The line of the course the move comes from
+    pub line: usize,
+    /// The state of the submarine before the move
+    pub position: Position,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the move on line {} overflows the submarine state from {:?}",
+            self.line, self.position
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+impl Position {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn at(hpos: i64, depth: i64, aim: i64) -> Self {
+        Position { aim, depth, hpos }
+    }
+
+    /// The puzzle answer : the horizontal position multiplied by the depth
+    pub fn answer(&self) -> i128 {
+        self.hpos as i128 * self.depth as i128
+    }
+}
+
+impl Move {
+    fn new(kind: MoveKind, unit: u32, line: usize) -> Self {
+        Move { kind, unit, line }
+    }
+
+    pub fn kind(&self) -> MoveKind {
+        self.kind
+    }
+
+    /// How far the move goes, 0 for `surface`
+    pub fn unit(&self) -> u32 {
+        self.unit
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+/// Writes the move the way courses are written
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let command = match self.kind {
+            MoveKind::Forward => "forward",
+            MoveKind::Back => "back",
+            MoveKind::Down => "down",
+            MoveKind::Up => "up",
+            MoveKind::Surface => return write!(f, "surface"),
+        };
+        write!(f, "{} {}", command, self.unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, VecDeque};
+
+    use common::parse::assert_parse_error_at;
+    use common::{Answer, Solver};
+
+    use crate::{
+        depth_profiles, navigate, parse_moves, plan, trace, Aimed, Current, Day02, Direct, Move,
+        Navigation, Plan, Position,
+    };
+
+    const CONTENT: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2";
+
+    #[test]
+    fn it_can_record_trajectories() {
+        let moves = parse_moves(CONTENT).unwrap();
+        let trajectory = trace(&moves, &Direct).unwrap();
+        let with_aim = trace(&moves, &Aimed).unwrap();
+
+        assert_eq!(trajectory.states().len(), 7);
+        assert_eq!(trajectory.states()[0], Position::new());
+        assert_eq!((trajectory.last().hpos, trajectory.last().depth), (15, 10));
+        assert_eq!(with_aim.last(), navigate(&moves, &Aimed).unwrap());
+        assert_eq!((with_aim.last().hpos, with_aim.last().depth), (15, 60));
+
+        let csv = with_aim.to_csv();
+        assert_eq!(csv.lines().count(), 8);
+        assert!(csv.starts_with("step,hpos,depth,aim\n0,0,0,0\n1,5,0,0\n2,5,0,5\n3,13,40,5\n"));
+
+        let svg = depth_profiles(&[("part 1", &trajectory), ("part 2 & aim", &with_aim)]);
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.contains("part 2 &amp; aim"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn it_can_run_extended_courses() {
+        let course = "# a hand written course\n\
+                      repeat 2 {\n  forward 3 # cruising\n  repeat 2 { down 1 }\n}\n\
+                      back 1\nup 10\nsurface\nup 2";
+        let moves = parse_moves(course).unwrap();
+        assert_eq!(moves.len(), 10);
+
+        let position = navigate(&moves, &Direct).unwrap();
+        assert_eq!((position.hpos, position.depth, position.aim), (5, -2, 0));
+        let position = navigate(&moves, &Aimed).unwrap();
+        assert_eq!((position.hpos, position.depth, position.aim), (5, 0, -2));
+        let position = navigate(&moves[..8], &Aimed).unwrap();
+        assert_eq!((position.hpos, position.depth, position.aim), (5, 2, -6));
+
+        // The dive of the last move is beyond an `i64`
+        let moves = parse_moves("down 4294967295\nforward 4294967295").unwrap();
+        let overflow = navigate(&moves, &Aimed).unwrap_err();
+        assert_eq!((overflow.line, overflow.position.aim), (2, 4294967295));
+        assert!(navigate(&moves, &Direct).is_ok());
+        assert_eq!(Day02::part2(&moves), Answer::Failed(overflow.to_string()));
+    }
+
+    #[test]
+    fn it_can_evaluate_a_course_against_every_model() {
+        let moves = parse_moves(CONTENT).unwrap();
+        let mut navigation = Navigation::new();
+        navigation.register(Current::new(Aimed, (1, -2)));
+
+        let results = navigation.evaluate(&moves);
+        let names: Vec<&str> = results.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["direct", "aimed", "aimed+current(1,-2)"]);
+        let answers: Vec<i128> = results
+            .iter()
+            .map(|(_, trajectory)| trajectory.as_ref().unwrap().last().answer())
+            .collect();
+        // The current pushes the submarine 6 forward & 12 up over the 6 moves
+        assert_eq!(answers, [150, 900, 21 * 48]);
+    }
+
+    #[test]
+    fn it_locates_course_errors() {
+        let errors = [
+            ("forward 5\nsideways 2", (2, 1)),
+            ("forward 5\ndown\nup 3", (2, 5)),
+            ("repeat 2 {\n  forward 1 # }\n", (1, 10)),
+            ("forward 1\n}", (2, 1)),
+            ("repeat x { up 1 }", (1, 8)),
+            ("repeat 2 up 1", (1, 10)),
+        ];
+        for (course, location) in errors {
+            assert_parse_error_at(parse_moves(course), location);
+        }
+    }
+
+    /// Runs the planned course written as text, so that the course is also checked to parse back
+    fn run(course: &[Move]) -> (i64, i64) {
+        let text: Vec<String> = course.iter().map(Move::to_string).collect();
+        let position = navigate(&parse_moves(&text.join("\n")).unwrap(), &Aimed).unwrap();
+        (position.hpos, position.depth)
+    }
+
+    #[test]
+    fn it_can_plan_courses_without_limit() {
+        let moves = |target| plan(target, None).unwrap().moves;
+        assert_eq!(moves((0, 0)).len(), 0);
+        assert_eq!(moves((15, 0)).len(), 1);
+        assert_eq!(moves((15, 60)).len(), 2);
+        assert_eq!(moves((15, -61)).len(), 3);
+        assert_eq!(run(&moves((1975, 633551))), (1975, 633551));
+        assert_eq!(run(&moves((15, -61))), (15, -61));
+
+        // Beyond the units of a `u32`, the course is searched for as with a limit
+        let far = plan((5_000_000_000, 0), None).unwrap();
+        assert_eq!(
+            Some(&far),
+            plan((5_000_000_000, 0), Some(u32::MAX)).as_ref()
+        );
+        assert!(far.proven_shortest);
+        assert_eq!(far.moves.len(), 2);
+        assert_eq!(run(&far.moves), (5_000_000_000, 0));
+
+        assert_eq!(plan((0, 3), None), None);
+        assert_eq!(plan((-1, 0), None), None);
+    }
+
+    #[test]
+    fn it_plans_shortest_courses_with_a_limit() {
+        const LIMIT: i64 = 3;
+        const MAX_HPOS: i64 = 5;
+        const MAX_DEPTH: i64 = 12;
+
+        // Breadth first search of every course of a few moves, from the start
+        let mut shortest: HashMap<(i64, i64), usize> = HashMap::new();
+        let mut seen = HashMap::from([((0, 0, 0), 0)]);
+        let mut queue = VecDeque::from([(0i64, 0i64, 0i64)]);
+        while let Some((hpos, depth, aim)) = queue.pop_front() {
+            let moves = seen[&(hpos, depth, aim)];
+            shortest.entry((hpos, depth)).or_insert(moves);
+            for unit in 1..=LIMIT {
+                for next in [
+                    (hpos + unit, depth + aim * unit, aim),
+                    (hpos, depth, aim + unit),
+                    (hpos, depth, aim - unit),
+                ] {
+                    if next.0 <= MAX_HPOS
+                        && next.1.abs() <= 4 * MAX_DEPTH
+                        && next.2.abs() <= 4 * MAX_DEPTH
+                        && !seen.contains_key(&next)
+                    {
+                        seen.insert(next, moves + 1);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        for hpos in 0..=MAX_HPOS {
+            for depth in -MAX_DEPTH..=MAX_DEPTH {
+                let course = plan((hpos, depth), Some(LIMIT as u32));
+                match shortest.get(&(hpos, depth)) {
+                    Some(moves) => {
+                        let course = course.unwrap();
+                        assert!(course.proven_shortest, "to {:?}", (hpos, depth));
+                        let course = course.moves;
+                        assert_eq!(course.len(), *moves, "to {:?}", (hpos, depth));
+                        assert!(course.iter().all(|mov| mov.unit() <= LIMIT as u32));
+                        assert_eq!(run(&course), (hpos, depth));
+                    }
+                    None => assert_eq!(course, None, "to {:?}", (hpos, depth)),
+                }
+            }
+        }
+
+        let course = plan((300, -77777), Some(3)).unwrap().moves;
+        assert_eq!(run(&course), (300, -77777));
+
+        // Far targets may end the search early, with a course at most 2 moves longer than the lower bound
+        for (target, limit) in [
+            ((20000, 123456789), 50),
+            ((5000, 4999999), 2),
+            ((2000, 1999999), 2),
+            ((1975, 633551), 100),
+            ((77, 1 << 40), 1 << 20),
+        ] {
+            let Plan {
+                moves: course,
+                proven_shortest,
+            } = plan(target, Some(limit)).unwrap();
+            assert_eq!(run(&course), target);
+            assert!(course.iter().all(|mov| mov.unit() <= limit));
+            let (hpos, depth) = (target.0 as u64, target.1.unsigned_abs());
+            let least = hpos.div_ceil(limit as u64) + depth.div_ceil(limit as u64 * hpos);
+            assert!(course.len() as u64 <= least + 2, "to {:?}", target);
+            assert!(
+                proven_shortest || course.len() as u64 > least,
+                "to {:?}",
+                target
+            );
+        }
+    }
+}
@@ -1,143 +1,109 @@
-use std::fs::File;
-use std::io::Read;
+use std::env;
+use std::fs;
 use std::path::Path;
 
-#[derive(Debug)]
-struct Position {
-    aim: u32,
-    depth: u32,
-    hpos: u32,
-}
-
-#[derive(Debug, Clone)]
-pub struct Move {
-    kind: MoveKind,
-    unit: u32,
-}
-
-impl Position {
-    fn new() -> Self {
-        Default::default()
-    }
-}
-
-impl Move {
-    fn new() -> Self {
-        Default::default()
-    }
-}
-
-#[derive(Debug, Clone)]
-enum MoveKind {
-    Forward,
-    Down,
-    Up,
-}
-
-impl From<&str> for Move {
-    fn from(input: &str) -> Self {
-        let mut iter = input.split(' ');
-        let mut mov = Move::new();
-
-        mov.kind = match iter.next() {
-            Some(maybe_move) => match maybe_move {
-                "forward" => MoveKind::Forward,
-                "down" => MoveKind::Down,
-                "up" => MoveKind::Up,
-                _ => {
-                    panic!("invalid move");
-                }
-            },
-            None => panic!("invalid input"),
-        };
-
-        mov.unit = match iter.next() {
-            Some(maybe_unit) => match maybe_unit.parse() {
-                Ok(unit) => unit,
-                Err(_e) => panic!("invalid unit"),
-            },
-            None => panic!("invalid input"),
-        };
-
-        mov
-    }
-}
+use common::{input, Solver};
+use day02::{depth_profiles, parse_moves, plan, Aimed, Current, Day02, Navigation, Trajectory};
 
-impl Default for Position {
-    fn default() -> Self {
-        Position {
-            aim: 0,
-            hpos: 0,
-            depth: 0,
+/// Arguments are `[INPUT] [--current HPOS,DEPTH] [--export DIR]` : the course is run through every navigation
+/// model, the `direct` one of part 1, the `aimed` one of part 2 & an `aimed` one drifting with the current
+/// when one is given. With `--export`, the trajectory of each model is written to
+/// `DIR` as `<model>.csv`, along with the `depth_profiles.svg` plot comparing them.
+///
+/// With `--plan HPOS,DEPTH [--limit UNITS]`, a shortest course reaching the target with the `aimed` model is
+/// printed instead, in the course language. A comment first tells whether it is proven to be the shortest.
+fn main() {
+    let mut args = env::args().skip(1);
+    let (mut input, mut export) = (None, None);
+    let (mut target, mut limit) = (None, None);
+    let mut navigation = Navigation::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--export" => export = args.next(),
+            "--current" => {
+                let drift = pair(args.next(), "`--current` takes a drift such as `1,-2`");
+                navigation.register(Current::new(Aimed, drift));
+            }
+            "--plan" => target = Some(pair(args.next(), "`--plan` takes a target such as `15,60`")),
+            "--limit" => {
+                limit = Some(
+                    args.next()
+                        .and_then(|n| n.parse().ok())
+                        .unwrap_or_else(|| input::exit("`--limit` takes a number of units")),
+                )
+            }
+            _ if input.is_none() => input = Some(arg),
+            _ => input::exit(format!("unexpected argument `{}`", arg)),
         }
     }
-}
 
-impl Default for Move {
-    fn default() -> Self {
-        Move {
-            kind: MoveKind::Forward,
-            unit: 0,
+    if let Some(target) = target {
+        match plan(target, limit) {
+            Some(plan) => {
+                if plan.proven_shortest {
+                    println!("# shortest course, {} moves", plan.moves.len());
+                } else {
+                    println!(
+                        "# {} moves, at most 2 more than the shortest course",
+                        plan.moves.len()
+                    );
+                }
+                plan.moves.iter().for_each(|mov| println!("{}", mov));
+            }
+            None => input::exit(format!(
+                "no course of forward, down & up moves reaches {:?}",
+                target
+            )),
         }
+        return;
     }
-}
-
-fn main() {
-    // Create a path to the desired file
-    let path = Path::new("input.txt");
-    let display = path.display();
-
-    // Open the path in read-only mode, returns `io::Result<File>`
-    let mut file = match File::open(&path) {
-        Err(why) => panic!("couldn't open {}: {}", display, why),
-        Ok(file) => file,
-    };
-
-    // Read the file contents into a string, returns `io::Result<usize>`
-    let mut content = String::new();
-    match file.read_to_string(&mut content) {
-        Err(why) => panic!("couldn't read {}: {}", display, why),
-        Ok(_) => (),
-    }
-
-    let moves: Vec<Move> = content.split('\n').map(|x| Move::from(x)).collect();
-    let moves_part2 = moves.clone();
-
-    let mut position_part1 = Position::new();
-    let mut position_part2 = Position::new();
 
-    // Part 1
-    for mov in moves {
-        match mov.kind {
-            MoveKind::Forward => position_part1.hpos += mov.unit,
-            MoveKind::Down => position_part1.depth += mov.unit,
-            MoveKind::Up => position_part1.depth -= mov.unit,
-        }
+    let content = input::load_or_exit(Day02::DAY, input.as_deref());
+
+    let moves = parse_moves(&content).unwrap_or_else(input::exit);
+
+    let mut trajectories = vec![];
+    for (name, trajectory) in navigation.evaluate(&moves) {
+        let trajectory =
+            trajectory.unwrap_or_else(|why| input::exit(format!("{} model: {}", name, why)));
+        let position = trajectory.last();
+        println!(
+            "{:>6} - At the end : Horizontal position is [{}], Depth is [{}] so the answer is [{}]",
+            name.to_uppercase(),
+            position.hpos,
+            position.depth,
+            position.answer()
+        );
+        trajectories.push((name, trajectory));
     }
 
-    println!(
-        "PART 1 - At the end : Horizontal position is [{}], Depth is [{}] so the ansmwer is [{}]",
-        position_part1.hpos,
-        position_part1.depth,
-        position_part1.hpos * position_part1.depth
-    );
-
-    // Part 2
-    for mov in moves_part2 {
-        match mov.kind {
-            MoveKind::Forward => {
-                position_part2.hpos += mov.unit;
-                position_part2.depth += position_part2.aim * mov.unit;
-            }
-            MoveKind::Down => position_part2.aim += mov.unit,
-            MoveKind::Up => position_part2.aim -= mov.unit,
+    if let Some(dir) = export {
+        let dir = Path::new(&dir);
+        let mut files: Vec<(String, String)> = trajectories
+            .iter()
+            .map(|(name, trajectory)| (format!("{}.csv", name), trajectory.to_csv()))
+            .collect();
+        let plotted: Vec<(&str, &Trajectory)> = trajectories
+            .iter()
+            .map(|(name, trajectory)| (*name, trajectory))
+            .collect();
+        files.push(("depth_profiles.svg".to_string(), depth_profiles(&plotted)));
+
+        for (name, content) in files {
+            let path = dir.join(name);
+            fs::write(&path, content).unwrap_or_else(|why| {
+                input::exit(format!("couldn't write {}: {}", path.display(), why))
+            });
+            println!("Wrote {}", path.display());
         }
     }
+}
 
-    println!(
-        "PART 2 - At the end : Horizontal position is [{}], Depth is [{}] so the ansmwer is [{}]",
-        position_part2.hpos,
-        position_part2.depth,
-        position_part2.hpos * position_part2.depth
-    );
+/// Parses a `X,Y` pair of numbers, or exits with `usage`
+fn pair(arg: Option<String>, usage: &str) -> (i64, i64) {
+    arg.and_then(|arg| {
+        let (x, y) = arg.split_once(',')?;
+        Some((x.parse().ok()?, y.parse().ok()?))
+    })
+    .unwrap_or_else(|| input::exit(usage))
 }
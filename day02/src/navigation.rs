@@ -0,0 +1,156 @@
+//! How the moves of a course change the state of the submarine.
+//!
+//! The puzzle gives two readings of the same course, `Direct` & `Aimed`, but any other model can be
+//! registered in a `Navigation` to be evaluated side by side with them on a single parsed course.
+
+use crate::{Move, MoveKind, Overflow, Position, Trajectory};
+
+pub trait NavigationModel {
+    /// Short name of the model, used to label its results
+    fn name(&self) -> &str;
+
+    /// The state of the submarine after `mov` from `position`, `None` when it can't be represented
+    fn step(&self, position: Position, mov: &Move) -> Option<Position>;
+}
+
+/// Part 1 : `down` and `up` directly change the depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Direct;
+
+impl NavigationModel for Direct {
+    fn name(&self) -> &str {
+        "direct"
+    }
+
+    fn step(&self, position: Position, mov: &Move) -> Option<Position> {
+        let unit = mov.unit() as i64;
+        let Position { hpos, depth, aim } = position;
+        Some(match mov.kind() {
+            MoveKind::Forward => Position::at(hpos.checked_add(unit)?, depth, aim),
+            MoveKind::Back => Position::at(hpos.checked_sub(unit)?, depth, aim),
+            MoveKind::Down => Position::at(hpos, depth.checked_add(unit)?, aim),
+            MoveKind::Up => Position::at(hpos, depth.checked_sub(unit)?, aim),
+            MoveKind::Surface => Position::at(hpos, 0, aim),
+        })
+    }
+}
+
+/// Part 2 : `down` and `up` change the aim, `forward` and `back` dive according to it.
+/// `surface` also levels the submarine out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Aimed;
+
+impl NavigationModel for Aimed {
+    fn name(&self) -> &str {
+        "aimed"
+    }
+
+    fn step(&self, position: Position, mov: &Move) -> Option<Position> {
+        let unit = mov.unit() as i64;
+        let Position { hpos, depth, aim } = position;
+        Some(match mov.kind() {
+            MoveKind::Forward => Position::at(
+                hpos.checked_add(unit)?,
+                depth.checked_add(aim.checked_mul(unit)?)?,
+                aim,
+            ),
+            MoveKind::Back => Position::at(
+                hpos.checked_sub(unit)?,
+                depth.checked_sub(aim.checked_mul(unit)?)?,
+                aim,
+            ),
+            MoveKind::Down => Position::at(hpos, depth, aim.checked_add(unit)?),
+            MoveKind::Up => Position::at(hpos, depth, aim.checked_sub(unit)?),
+            MoveKind::Surface => Position::at(hpos, 0, 0),
+        })
+    }
+}
+
+/// Another model, with a current that drifts the submarine by `(hpos, depth)` after every move
+pub struct Current<M> {
+    name: String,
+    model: M,
+    drift: (i64, i64),
+}
+
+impl<M: NavigationModel> Current<M> {
+    pub fn new(model: M, drift: (i64, i64)) -> Self {
+        Current {
+            name: format!("{}+current({},{})", model.name(), drift.0, drift.1),
+            model,
+            drift,
+        }
+    }
+}
+
+impl<M: NavigationModel> NavigationModel for Current<M> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn step(&self, position: Position, mov: &Move) -> Option<Position> {
+        let moved = self.model.step(position, mov)?;
+        Some(Position::at(
+            moved.hpos.checked_add(self.drift.0)?,
+            moved.depth.checked_add(self.drift.1)?,
+            moved.aim,
+        ))
+    }
+}
+
+/// Where the submarine ends when `model` runs `moves`
+pub fn navigate(moves: &[Move], model: &dyn NavigationModel) -> Result<Position, Overflow> {
+    moves.iter().try_fold(Position::new(), |position, mov| {
+        advance(model, position, mov)
+    })
+}
+
+/// Every state of the submarine when `model` runs `moves`
+pub fn trace(moves: &[Move], model: &dyn NavigationModel) -> Result<Trajectory, Overflow> {
+    Trajectory::record(moves, |position, mov| advance(model, position, mov))
+}
+
+fn advance(
+    model: &dyn NavigationModel,
+    position: Position,
+    mov: &Move,
+) -> Result<Position, Overflow> {
+    model.step(position, mov).ok_or(Overflow {
+        line: mov.line(),
+        position,
+    })
+}
+
+/// The models a course is evaluated against, the puzzle ones by default
+pub struct Navigation {
+    models: Vec<Box<dyn NavigationModel>>,
+}
+
+impl Default for Navigation {
+    fn default() -> Self {
+        Navigation {
+            models: vec![Box::new(Direct), Box::new(Aimed)],
+        }
+    }
+}
+
+impl Navigation {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn register(&mut self, model: impl NavigationModel + 'static) {
+        self.models.push(Box::new(model));
+    }
+
+    pub fn models(&self) -> impl Iterator<Item = &dyn NavigationModel> {
+        self.models.iter().map(|model| model.as_ref())
+    }
+
+    /// The trajectory of `moves` through every model, in the order they were registered
+    pub fn evaluate<'a>(&'a self, moves: &[Move]) -> Vec<(&'a str, Result<Trajectory, Overflow>)> {
+        self.models()
+            .map(|model| (model.name(), trace(moves, model)))
+            .collect()
+    }
+}
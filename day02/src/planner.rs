@@ -0,0 +1,226 @@
+//! The inverse of navigation : the shortest course of `forward`, `down` & `up` moves reaching a target
+//! under the `Aimed` model.
+//!
+//! Read backwards, a course is simple : an aim change of `a` made while `r` units of `forward` moves are
+//! still to come adds `a * r` to the final depth. Planning is then a shortest path over the
+//! `(forward units still to come, depth still to gain)` states, which A* explores knowing that a move
+//! covers at most `limit` units. When the units of a `u32` are enough, a course never needs more than 3
+//! moves & is built directly.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::{Move, MoveKind};
+
+/// Forward units still to come & depth still to gain
+type State = (u64, i128);
+
+/// How many states the search keeps queued & how many moves it tries before settling for the fallback course,
+/// so that planning takes a bounded time & memory whatever the target
+const MAX_STATES: usize = 200_000;
+const MAX_MOVES_TRIED: usize = 10_000_000;
+
+/// A course reaching the target of `plan`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub moves: Vec<Move>,
+    /// `false` when the search stopped at its bounds : the course is then at most 2 moves longer than the
+    /// shortest one
+    pub proven_shortest: bool,
+}
+
+/// A course ending at `(hpos, depth)`, with moves of at most `limit` units, or of a `u32` when there is no
+/// limit. `None` when the target can't be reached : behind the start, or below or above it without moving
+/// forward.
+///
+/// A target too far for the search to finish within its bounds gets a course at most 2 moves longer than
+/// the shortest one, which `Plan::proven_shortest` tells.
+pub fn plan(target: (i64, i64), limit: Option<u32>) -> Option<Plan> {
+    let hpos = u64::try_from(target.0).ok()?;
+    let depth = target.1 as i128;
+    if hpos == 0 && depth != 0 {
+        return None;
+    }
+
+    let limit = limit.unwrap_or(u32::MAX);
+    let direct =
+        direct_course(hpos, depth).filter(|course| course.iter().all(|(_, unit)| *unit <= limit));
+    let (course, proven_shortest) = match direct {
+        Some(course) => (course, true),
+        None if limit > 0 => search(hpos, depth, limit),
+        None => return None,
+    };
+    Some(Plan {
+        moves: course
+            .into_iter()
+            .enumerate()
+            .map(|(i, (kind, unit))| Move::new(kind, unit, i + 1))
+            .collect(),
+        proven_shortest,
+    })
+}
+
+/// The shortest course when units are only limited by a `u32` :
+/// - nothing to do at the start,
+/// - `forward` at the surface,
+/// - one aim change then `forward` when the depth is a multiple of the distance,
+/// - otherwise `forward` up to the last unit, where an aim change of the whole depth then takes place.
+fn direct_course(hpos: u64, depth: i128) -> Option<Vec<(MoveKind, u32)>> {
+    let unit = |n: i128| u32::try_from(n.unsigned_abs()).ok();
+    let aim = |n: i128| Some((aim_kind(n), unit(n)?));
+    let hpos = hpos as i128;
+
+    Some(match (hpos, depth) {
+        (0, _) => vec![],
+        (_, 0) => vec![(MoveKind::Forward, unit(hpos)?)],
+        _ if depth % hpos == 0 => vec![aim(depth / hpos)?, (MoveKind::Forward, unit(hpos)?)],
+        _ => vec![
+            (MoveKind::Forward, unit(hpos - 1)?),
+            aim(depth)?,
+            (MoveKind::Forward, 1),
+        ],
+    })
+}
+
+/// A* over the states, from `(hpos, depth)` down to `(0, 0)`.
+///
+/// The estimate of a state is how many moves its forward units need at least, plus how many aim changes
+/// its depth needs at least, the remaining distance being the most an aim unit is worth. It is also
+/// what the whole course needs at least, & `fallback_course` needs at most 2 more moves : only the states
+/// that could beat it are explored, & the fallback is kept when it can't be beaten or the search gets too
+/// large. The course comes with whether it is proven to be the shortest, which only the search getting too
+/// large leaves unknown.
+fn search(hpos: u64, depth: i128, limit: u32) -> (Vec<(MoveKind, u32)>, bool) {
+    let estimate = |(remaining, depth): State| -> u64 {
+        if remaining == 0 {
+            return 0;
+        }
+        let reach = limit as u128 * remaining as u128;
+        remaining.div_ceil(limit as u64) + depth.unsigned_abs().div_ceil(reach) as u64
+    };
+    let fallback = fallback_course(hpos, depth, limit);
+    let bound = fallback.len() as u64;
+    let start = (hpos, depth);
+    // The estimate is a lower bound, the fallback is then already as short as can be
+    if estimate(start) >= bound {
+        return (fallback, true);
+    }
+
+    let mut costs: HashMap<State, u64> = HashMap::from([(start, 0)]);
+    let mut parents: HashMap<State, (State, MoveKind, u32)> = HashMap::new();
+    // The deepest states first among equals, they are the closest to the end
+    let mut queue = BinaryHeap::from([Reverse((estimate(start), Reverse(0), start))]);
+    let mut tried = 0;
+
+    while let Some(Reverse((_, Reverse(cost), state))) = queue.pop() {
+        if state == (0, 0) {
+            return (path(&parents, state), true);
+        }
+        if cost > costs[&state] {
+            continue;
+        }
+
+        let (remaining, depth) = state;
+        let forwards = (1..=remaining.min(limit as u64))
+            .map(|unit| ((remaining - unit, depth), MoveKind::Forward, unit as u32));
+        let aims = (1..=limit).filter(|_| remaining > 0).flat_map(|unit| {
+            let gain = unit as i128 * remaining as i128;
+            [
+                ((remaining, depth - gain), MoveKind::Down, unit),
+                ((remaining, depth + gain), MoveKind::Up, unit),
+            ]
+        });
+        for (next, kind, unit) in forwards.chain(aims) {
+            tried += 1;
+            if costs.len().max(queue.len()) > MAX_STATES || tried > MAX_MOVES_TRIED {
+                return (fallback, false);
+            }
+            // Nothing changes the depth once every forward move is done
+            if next.0 == 0 && next.1 != 0 {
+                continue;
+            }
+            let total = cost + 1 + estimate(next);
+            if total >= bound || costs.get(&next).is_some_and(|known| *known <= cost + 1) {
+                continue;
+            }
+            costs.insert(next, cost + 1);
+            parents.insert(next, (state, kind, unit));
+            queue.push(Reverse((total, Reverse(cost + 1), next)));
+        }
+    }
+    // Every state that could beat the fallback was explored
+    (fallback, true)
+}
+
+/// A course that is at most 2 moves longer than the shortest one : the aim is first set to the multiple
+/// of the distance just below or above the depth, then a single unit of aim makes up for the rest when the
+/// remaining distance is what the depth still lacks or exceeds.
+fn fallback_course(hpos: u64, depth: i128, limit: u32) -> Vec<(MoveKind, u32)> {
+    let distance = hpos as i128;
+    let (quotient, rest) = (depth.div_euclid(distance), depth.rem_euclid(distance));
+    let below = split_course(quotient, rest as u64, MoveKind::Down, hpos, limit);
+    if rest == 0 {
+        return below;
+    }
+    let above = split_course(
+        quotient + 1,
+        (distance - rest) as u64,
+        MoveKind::Up,
+        hpos,
+        limit,
+    );
+    if above.len() < below.len() {
+        above
+    } else {
+        below
+    }
+}
+
+/// Sets the aim to `aim`, goes `forward` until `at` units remain, changes the aim by one unit with
+/// `correction` there & goes `forward` to the end, every move being cut to `limit` units
+fn split_course(
+    aim: i128,
+    at: u64,
+    correction: MoveKind,
+    hpos: u64,
+    limit: u32,
+) -> Vec<(MoveKind, u32)> {
+    let mut course = vec![];
+    let mut push = |kind: MoveKind, amount: u128| {
+        let mut amount = amount;
+        while amount > 0 {
+            let unit = amount.min(limit as u128);
+            course.push((kind, unit as u32));
+            amount -= unit;
+        }
+    };
+
+    push(aim_kind(aim), aim.unsigned_abs());
+    push(MoveKind::Forward, (hpos - at) as u128);
+    if at > 0 {
+        push(correction, 1);
+    }
+    push(MoveKind::Forward, at as u128);
+    course
+}
+
+/// The aim change going `n` units down, or up when it is negative
+fn aim_kind(n: i128) -> MoveKind {
+    if n > 0 {
+        MoveKind::Down
+    } else {
+        MoveKind::Up
+    }
+}
+
+/// The moves leading to `state`, from the start
+fn path(parents: &HashMap<State, (State, MoveKind, u32)>, state: State) -> Vec<(MoveKind, u32)> {
+    let mut course = vec![];
+    let mut state = state;
+    while let Some(&(previous, kind, unit)) = parents.get(&state) {
+        course.push((kind, unit));
+        state = previous;
+    }
+    course.reverse();
+    course
+}
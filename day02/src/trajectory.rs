@@ -0,0 +1,149 @@
+//! Every state the submarine goes through along a course, exportable to check the course by eye.
+
+use std::fmt::Write;
+
+use crate::{Move, Overflow, Position};
+
+/// The states of the submarine along a course, the starting one included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    states: Vec<Position>,
+}
+
+/// Size of the SVG plot, in pixels
+const PLOT_WIDTH: f64 = 800.0;
+const PANEL_HEIGHT: f64 = 200.0;
+const MARGIN: f64 = 40.0;
+
+/// Colours of the successive trajectories of a plot
+const COLOURS: [&str; 4] = ["#1f77b4", "#d62728", "#2ca02c", "#9467bd"];
+
+impl Trajectory {
+    /// Records the states of the submarine applying `moves` one after the other with `step`
+    pub fn record(
+        moves: &[Move],
+        step: impl Fn(Position, &Move) -> Result<Position, Overflow>,
+    ) -> Result<Self, Overflow> {
+        let mut position = Position::new();
+        let mut states = Vec::with_capacity(moves.len() + 1);
+        states.push(position);
+        for mov in moves {
+            position = step(position, mov)?;
+            states.push(position);
+        }
+        Ok(Trajectory { states })
+    }
+
+    pub fn states(&self) -> &[Position] {
+        &self.states
+    }
+
+    /// Where the course ends
+    pub fn last(&self) -> Position {
+        self.states[self.states.len() - 1]
+    }
+
+    /// One `step,hpos,depth,aim` row per state, after a header
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,hpos,depth,aim\n");
+        for (step, state) in self.states.iter().enumerate() {
+            writeln!(csv, "{},{},{},{}", step, state.hpos, state.depth, state.aim).unwrap();
+        }
+        csv
+    }
+}
+
+/// Plots the depth of each named trajectory against its horizontal position, one panel per trajectory :
+/// their depths can be orders of magnitude apart, so each panel has its own depth scale.
+pub fn depth_profiles(trajectories: &[(&str, &Trajectory)]) -> String {
+    let height = MARGIN + trajectories.len() as f64 * (PANEL_HEIGHT + MARGIN);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        PLOT_WIDTH + 2.0 * MARGIN,
+        height
+    );
+
+    let hpos = range(
+        trajectories
+            .iter()
+            .flat_map(|(_, trajectory)| trajectory.states.iter().map(|state| state.hpos)),
+    );
+    for (i, (name, trajectory)) in trajectories.iter().enumerate() {
+        let top = MARGIN + i as f64 * (PANEL_HEIGHT + MARGIN);
+        let depth = range(trajectory.states.iter().map(|state| state.depth));
+
+        // Depths grow downwards, the surface is drawn when the submarine goes above it
+        writeln!(
+            svg,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#999\"/>",
+            MARGIN, top, PLOT_WIDTH, PANEL_HEIGHT
+        )
+        .unwrap();
+        if depth.0 < 0 {
+            let y = top + scale(0, depth) * PANEL_HEIGHT;
+            writeln!(
+                svg,
+                "  <line x1=\"{}\" y1=\"{:.1}\" x2=\"{}\" y2=\"{:.1}\" stroke=\"#999\" stroke-dasharray=\"4\"/>",
+                MARGIN,
+                y,
+                MARGIN + PLOT_WIDTH,
+                y
+            )
+            .unwrap();
+        }
+        writeln!(
+            svg,
+            "  <text x=\"{}\" y=\"{}\">{} (depth {} to {}, hpos {} to {})</text>",
+            MARGIN,
+            top - 8.0,
+            escape(name),
+            depth.0,
+            depth.1,
+            hpos.0,
+            hpos.1
+        )
+        .unwrap();
+
+        let points: Vec<String> = trajectory
+            .states
+            .iter()
+            .map(|state| {
+                let x = MARGIN + scale(state.hpos, hpos) * PLOT_WIDTH;
+                let y = top + scale(state.depth, depth) * PANEL_HEIGHT;
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect();
+        writeln!(
+            svg,
+            "  <polyline fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\" points=\"{}\"/>",
+            COLOURS[i % COLOURS.len()],
+            points.join(" ")
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The lowest & highest of `values`, always including 0
+fn range(values: impl Iterator<Item = i64>) -> (i64, i64) {
+    values.fold((0, 0), |(low, high), value| {
+        (low.min(value), high.max(value))
+    })
+}
+
+/// Where `value` lies in `range`, from 0 at its low end to 1 at its high end
+fn scale(value: i64, (low, high): (i64, i64)) -> f64 {
+    if low == high {
+        return 0.0;
+    }
+    (value as f64 - low as f64) / (high as f64 - low as f64)
+}
+
+/// `text` made safe to be written in an SVG element
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,141 @@
+//! Packed bits, 64 to a word, so that counting them is one popcount per word.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    len: usize,
+    /// Bit `i` is bit `i % 64` of word `i / 64`, the bits past `len` are always 0
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    /// `len` bits, all 0
+    pub fn zeros(len: usize) -> Self {
+        BitSet {
+            len,
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    /// `len` bits, all 1
+    pub fn ones(len: usize) -> Self {
+        let mut bits = BitSet {
+            len,
+            words: vec![u64::MAX; len.div_ceil(64)],
+        };
+        bits.clear_tail();
+        bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "bit {} is out of the {} bits", i, self.len);
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len, "bit {} is out of the {} bits", i, self.len);
+        if value {
+            self.words[i / 64] |= 1 << (i % 64);
+        } else {
+            self.words[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    /// How many bits are 1
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// How many bits are 1 in both sets, which must have the same length
+    pub fn count_ones_and(&self, other: &BitSet) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    /// Keeps the bits that are also 1 in `other`, or 0 in `other` when `value` is false
+    pub fn retain(&mut self, other: &BitSet, value: bool) {
+        for (word, mask) in self.words.iter_mut().zip(&other.words) {
+            *word &= if value { *mask } else { !*mask };
+        }
+        self.clear_tail();
+    }
+
+    /// The index of the first bit that is 1
+    pub fn first_one(&self) -> Option<usize> {
+        self.words
+            .iter()
+            .enumerate()
+            .find(|(_, word)| **word != 0)
+            .map(|(i, word)| i * 64 + word.trailing_zeros() as usize)
+    }
+
+    /// Among the last `last` bits that are 1, or all of them when there are fewer : how many there are & how
+    /// many are also 1 in `other`, which must have the same length. Only the words holding them are read.
+    pub fn count_last_ones_and(&self, other: &BitSet, last: usize) -> (usize, usize) {
+        let (mut counted, mut both) = (0, 0);
+        for (word, mask) in self.words.iter().zip(&other.words).rev() {
+            if counted == last {
+                break;
+            }
+            let mut word = *word;
+            // Only the highest ones of the word are among the last ones
+            for _ in last..counted + word.count_ones() as usize {
+                word &= word - 1;
+            }
+            counted += word.count_ones() as usize;
+            both += (word & mask).count_ones() as usize;
+        }
+        (counted, both)
+    }
+
+    /// Every bit flipped
+    pub fn not(&self) -> BitSet {
+        let mut bits = BitSet {
+            len: self.len,
+            words: self.words.iter().map(|word| !word).collect(),
+        };
+        bits.clear_tail();
+        bits
+    }
+
+    /// The bits read as a binary number, bit 0 being the most significant. `None` past 128 bits.
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.len > 128 {
+            return None;
+        }
+        Some((0..self.len).fold(0, |n, i| n << 1 | self.get(i) as u128))
+    }
+
+    fn clear_tail(&mut self) {
+        if !self.len.is_multiple_of(64) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (self.len % 64)) - 1;
+            }
+        }
+    }
+}
+
+/// Writes the bits as a binary number, bit 0 first
+impl fmt::Display for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits: String = (0..self.len)
+            .map(|i| if self.get(i) { '1' } else { '0' })
+            .collect();
+        f.pad(&digits)
+    }
+}
@@ -0,0 +1,323 @@
+use common::{parse, Answer, Result, Solver};
+
+mod bits;
+mod query;
+
+pub use bits::BitSet;
+pub use query::{parse_rules, select_all, BitCriteria, Rule, Selection, Step};
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Report;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        Report::parse(content)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let (gamma, epsilon) = power_rates(input);
+        product(&gamma, &epsilon)
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        let (oxygen, co2) = life_support_ratings(input);
+        product(&oxygen, &co2)
+    }
+}
+
+/// The diagnostic report : binary readings that all have the same width, stored column by column so
+/// that the readings with a given bit set are counted a word at a time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    width: usize,
+    len: usize,
+    /// `columns[j]` holds bit `j` of every reading, bit 0 being the leftmost one
+    columns: Vec<BitSet>,
+}
+
+impl Report {
+    /// Parses one binary number per line, the first one giving the width of the report
+    pub fn parse(content: &str) -> Result<Self> {
+        let lines: Vec<&str> = content.split('\n').collect();
+        let width = lines[0].len();
+        let mut columns = vec![BitSet::zeros(lines.len()); width];
+
+        for (i, line) in lines.iter().enumerate() {
+            if let Some((j, c)) = line.char_indices().find(|(_, c)| *c != '0' && *c != '1') {
+                return Err(parse::error(
+                    content,
+                    &line[j..j + c.len_utf8()],
+                    "a binary digit",
+                ));
+            }
+            match line.len() {
+                0 => return Err(parse::error(content, line, "a binary number")),
+                n if n > width => {
+                    return Err(parse::error(
+                        content,
+                        &line[width..],
+                        format!("the end of the {} bits wide reading", width),
+                    ))
+                }
+                n if n < width => {
+                    return Err(parse::error(
+                        content,
+                        &line[n..],
+                        format!("{} bits per reading", width),
+                    ))
+                }
+                _ => (),
+            }
+            for (j, bit) in line.bytes().enumerate() {
+                if bit == b'1' {
+                    columns[j].set(i, true);
+                }
+            }
+        }
+
+        Ok(Report {
+            width,
+            len: lines.len(),
+            columns,
+        })
+    }
+
+    /// How many bits every reading has
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// How many readings the report holds
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The readings with bit `j` set
+    pub fn column(&self, j: usize) -> &BitSet {
+        &self.columns[j]
+    }
+
+    /// Reading `i`, in the order of the report
+    pub fn reading(&self, i: usize) -> BitSet {
+        let mut reading = BitSet::zeros(self.width);
+        for (j, column) in self.columns.iter().enumerate() {
+            reading.set(j, column.get(i));
+        }
+        reading
+    }
+}
+
+/// Part 1 : returns the gamma & epsilon rates.
+pub fn power_rates(report: &Report) -> (BitSet, BitSet) {
+    let mut gamma = BitSet::zeros(report.width);
+    for j in 0..report.width {
+        let ones = report.columns[j].count_ones();
+        gamma.set(j, ones >= report.len - ones);
+    }
+    let epsilon = gamma.not();
+    (gamma, epsilon)
+}
+
+/// Part 2 : returns the oxygen generator & CO2 scrubber ratings.
+pub fn life_support_ratings(report: &Report) -> (BitSet, BitSet) {
+    let selections = select_all(report, &[Rule::oxygen(), Rule::co2()]);
+    (
+        report.reading(selections[0].reading),
+        report.reading(selections[1].reading),
+    )
+}
+
+/// The answer of a part, unsolved when the rates are too wide to be multiplied
+fn product(a: &BitSet, b: &BitSet) -> Answer {
+    match (a.to_u128(), b.to_u128()) {
+        (Some(a), Some(b)) => a
+            .checked_mul(b)
+            .and_then(|n| i128::try_from(n).ok())
+            .map_or(Answer::Unsolved, Answer::Number),
+        _ => Answer::Unsolved,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        life_support_ratings, parse_rules, power_rates, select_all, BitCriteria, BitSet, Report,
+        Rule, Step,
+    };
+    use common::parse::assert_parse_error_at;
+
+    const CONTENT: &str =
+        "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010";
+
+    #[test]
+    fn it_detects_the_width_of_the_report() {
+        let report = Report::parse(CONTENT).unwrap();
+        assert_eq!((report.width(), report.len()), (5, 12));
+
+        let (gamma, epsilon) = power_rates(&report);
+        assert_eq!(
+            (gamma.to_string(), epsilon.to_string()),
+            ("10110".into(), "01001".into())
+        );
+        let (oxygen, co2) = life_support_ratings(&report);
+        assert_eq!((oxygen.to_u128(), co2.to_u128()), (Some(23), Some(10)));
+
+        assert!(Report::parse("0101\n010").is_err());
+        assert!(Report::parse("0101\n01011").is_err());
+        assert!(Report::parse("0101\n0121").is_err());
+    }
+
+    #[test]
+    fn it_handles_readings_wider_than_a_word() {
+        let wide: Vec<String> = (0..100)
+            .map(|i| format!("{}{:070b}{}", i % 2, i * i, "1".repeat(60)))
+            .collect();
+        let report = Report::parse(&wide.join("\n")).unwrap();
+        assert_eq!((report.width(), report.len()), (131, 100));
+        assert_eq!(report.reading(7).to_string(), wide[7]);
+
+        let (gamma, _) = power_rates(&report);
+        assert!(gamma.to_string().ends_with(&"1".repeat(60)));
+        assert_eq!(gamma.to_u128(), None);
+        let (oxygen, co2) = life_support_ratings(&report);
+        assert!(wide.contains(&oxygen.to_string()));
+        assert!(wide.contains(&co2.to_string()));
+    }
+
+    #[test]
+    fn it_rates_with_any_bit_criteria() {
+        let rate = |content: &str, criteria: &[BitCriteria]| {
+            let report = Report::parse(content).unwrap();
+            let rules: Vec<Rule> = criteria.iter().map(|c| Rule::new("r", *c)).collect();
+            select_all(&report, &rules)
+                .iter()
+                .map(|selection| report.reading(selection.reading).to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            rate(
+                CONTENT,
+                &[
+                    BitCriteria::OXYGEN,
+                    BitCriteria::CO2,
+                    BitCriteria::MostCommon { tie: false },
+                    BitCriteria::LeastCommon { tie: true },
+                ]
+            ),
+            // The last two ratings end on a tie, between 10110 & 10111 and between 01111 & 01010
+            ["10111", "01010", "10110", "01111"]
+        );
+
+        // The least common bit is the one every candidate has when no candidate has the other
+        assert_eq!(rate("000\n001\n001\n011", &[BitCriteria::CO2]), ["011"]);
+        assert_eq!(rate("110\n110\n111", &[BitCriteria::CO2]), ["111"]);
+    }
+
+    #[test]
+    fn it_counts_the_last_candidates_a_word_at_a_time() {
+        let mut candidates = BitSet::zeros(200);
+        let mut column = BitSet::zeros(200);
+        for i in (0..200).step_by(3) {
+            candidates.set(i, true);
+            column.set(i, i % 2 == 0);
+        }
+        for last in [0, 1, 5, 22, 23, 40, 67, 68, 1000] {
+            let counted: Vec<usize> = (0..200)
+                .rev()
+                .filter(|i| candidates.get(*i))
+                .take(last)
+                .collect();
+            let ones = counted.iter().filter(|i| column.get(**i)).count();
+            assert_eq!(
+                candidates.count_last_ones_and(&column, last),
+                (counted.len(), ones),
+                "last {}",
+                last
+            );
+        }
+    }
+
+    #[test]
+    fn it_selects_readings_with_rules() {
+        let report = Report::parse(CONTENT).unwrap();
+        let rules = parse_rules(
+            "# the puzzle ones\noxygen : most\nco2 : least tie 0\n\nquiet : least tie 1 skip 3-5 # bits 3 to 5\nx : least skip 0,1 last 3",
+        )
+        .unwrap();
+        assert_eq!(
+            rules
+                .iter()
+                .map(|rule| rule.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "oxygen : most tie 1",
+                "co2 : least tie 0",
+                "quiet : least tie 1 skip 3-5",
+                "x : least tie 0 skip 0,1 last 3"
+            ]
+        );
+
+        // The puzzle ratings are the same rules
+        assert_eq!(rules[0].to_string(), Rule::oxygen().to_string());
+        assert_eq!(rules[1].to_string(), Rule::co2().to_string());
+        let (oxygen, co2) = life_support_ratings(&report);
+        assert_eq!(report.reading(rules[0].select(&report).reading), oxygen);
+        let selection = rules[1].select(&report);
+        assert_eq!(report.reading(selection.reading), co2);
+        assert_eq!(
+            selection.trace[1],
+            Step {
+                bit: 1,
+                zeros: 3,
+                ones: 2,
+                kept: true,
+                eliminated: 3,
+                remaining: 2
+            }
+        );
+
+        let selection = rules[2].select(&report);
+        assert_eq!(report.reading(selection.reading).to_string(), "01111");
+        assert_eq!(selection.trace.len(), 3);
+
+        // Only the last 3 candidates count : 11001, 00010 & 01010 have no 1 on bit 2, so the 8 readings
+        // having one are kept
+        let selection = rules[3].select(&report);
+        assert_eq!(
+            selection
+                .trace
+                .iter()
+                .map(|step| (step.bit, step.zeros, step.ones))
+                .collect::<Vec<_>>(),
+            [(2, 3, 0), (3, 1, 2), (4, 2, 1)]
+        );
+        assert_eq!((selection.reading, selection.candidates), (4, 1));
+
+        // Readings that only differ on skipped bits are all left
+        let rules = parse_rules("y : least tie 1 skip 0,1").unwrap();
+        let selection = rules[0].select(&report);
+        assert_eq!((selection.reading, selection.candidates), (10, 2));
+    }
+
+    #[test]
+    fn it_locates_errors_in_rules() {
+        for (rules, position) in [
+            ("a : most\nb : lest", (2, 5)),
+            ("most", (1, 5)),
+            (" : most", (1, 2)),
+            ("a : least tie 2", (1, 15)),
+            ("a : most skip 5-3", (1, 15)),
+            ("a : most last 0", (1, 15)),
+            ("a : tie 1", (1, 10)),
+        ] {
+            assert_parse_error_at(parse_rules(rules), position);
+        }
+    }
+}
@@ -1,143 +1,91 @@
-use std::fs::File;
-use std::io::Read;
-use std::path::Path;
+use std::env;
+use std::fs;
 
-pub fn main() {
-    // Create a path to the desired file
-    let path = Path::new("input.txt");
-    let display = path.display();
-
-    // Open the path in read-only mode, returns `io::Result<File>`
-    let mut file = match File::open(&path) {
-        Err(why) => panic!("couldn't open {}: {}", display, why),
-        Ok(file) => file,
-    };
-
-    // Read the file contents into a string, returns `io::Result<usize>`
-    let mut content = String::new();
-    match file.read_to_string(&mut content) {
-        Err(why) => panic!("couldn't read {}: {}", display, why),
-        Ok(_) => (),
-    }
-
-    let mut frequency_array = [[0 as u16; 2]; 12];
+use common::{input, Error, Solver};
+use day03::{life_support_ratings, parse_rules, power_rates, Day03, Report, Rule};
 
-    // Part 1
-    for bin_number in content.split('\n') {
-        for (pos, bit) in bin_number.chars().enumerate() {
-            match bit {
-                '0' => frequency_array[pos][0] += 1,
-                '1' => frequency_array[pos][1] += 1,
-                _ => panic!("invalid binary number"),
+/// Arguments are `[INPUT] [--rule RULE]... [--rules FILE]` : besides the puzzle answers, the reading
+/// selected by each rule written in the rules language is printed along with how the candidates were
+/// eliminated, bit after bit.
+pub fn main() {
+    let mut args = env::args().skip(1);
+    let (mut input, mut rules) = (None, vec![]);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rule" | "--rules" => {
+                let Some(value) = args.next() else {
+                    input::exit(format!(
+                        "`{}` takes a rule such as `quiet : least skip 3-5`, or a file of rules",
+                        arg
+                    ))
+                };
+                let (origin, spec) = match arg.as_str() {
+                    "--rule" => ("--rule".to_string(), value),
+                    _ => {
+                        let spec = fs::read_to_string(&value).unwrap_or_else(|why| {
+                            input::exit(format!("couldn't read {}: {}", value, why))
+                        });
+                        (value, spec)
+                    }
+                };
+                rules.extend(parse_rules(&spec).unwrap_or_else(|why| match why {
+                    Error::Parse(why) => input::exit(why.render(&origin, &spec)),
+                    why => input::exit(why),
+                }));
             }
+            _ if input.is_none() => input = Some(arg),
+            _ => input::exit(format!("unexpected argument `{}`", arg)),
         }
     }
 
-    let binary_rates: (String, String) = extract_binary_rates(frequency_array);
+    let content = input::load_or_exit(Day03::DAY, input.as_deref());
 
-    let gamma = u32::from_str_radix(binary_rates.0.as_str(), 2).unwrap();
-    let epsilon = u32::from_str_radix(binary_rates.1.as_str(), 2).unwrap();
+    let report = Day03::parse(&content).unwrap_or_else(input::exit);
+
+    // Part 1
+    let (gamma, epsilon) = power_rates(&report);
 
     println!(
-        "PART 1 - At the end : gamma & epsilon are [{:?}, {:?}] so number is [{}]",
+        "PART 1 - At the end : gamma & epsilon are [{}, {}] so number is [{}]",
         gamma,
         epsilon,
-        gamma * epsilon
+        Day03::part1(&report)
     );
 
     // Part 2
-    let split_content_oxygen: Vec<&str> = content.split('\n').collect();
-
-    let mut oxygen_rating = String::new();
-
-    for i in 0..=11 {
-        let mut zero_count = 0;
-        let mut one_count = 0;
-        let mut candidates_count = 0;
-        let mut last_candidate: &str = "";
-        for bin_number in &split_content_oxygen {
-            if !bin_number.starts_with(&oxygen_rating) {
-                continue;
-            }
-            candidates_count += 1;
-            last_candidate = bin_number;
-            match bin_number.chars().nth(i) {
-                Some(c) => match c {
-                    '0' => zero_count += 1,
-                    '1' => one_count += 1,
-                    _ => panic!("invalid binary number"),
-                },
-                None => (),
-            }
-        }
-        if candidates_count == 1 {
-            oxygen_rating = last_candidate.to_string();
-            break;
-        }
-        if zero_count > one_count {
-            oxygen_rating.push('0')
-        } else {
-            oxygen_rating.push('1')
-        }
-    }
-
-    let mut co2_rating = String::new();
-
-    for i in 0..=11 {
-        let mut zero_count = 0;
-        let mut one_count = 0;
-        let mut candidates_count = 0;
-        let mut last_candidate: &str = "";
-        for bin_number in &split_content_oxygen {
-            if !bin_number.starts_with(&co2_rating) {
-                continue;
-            }
-            candidates_count += 1;
-            last_candidate = bin_number;
-            match bin_number.chars().nth(i) {
-                Some(c) => match c {
-                    '0' => zero_count += 1,
-                    '1' => one_count += 1,
-                    _ => panic!("invalid binary number"),
-                },
-                None => (),
-            }
-        }
-        if candidates_count == 1 {
-            co2_rating = last_candidate.to_string();
-            break;
-        }
-        if zero_count > one_count {
-            co2_rating.push('1')
-        } else {
-            co2_rating.push('0')
-        }
-    }
-
-    let oxygen = u32::from_str_radix(oxygen_rating.as_str(), 2).unwrap();
-    let co2 = u32::from_str_radix(co2_rating.as_str(), 2).unwrap();
+    let (oxygen, co2) = life_support_ratings(&report);
 
     println!(
-        "PART 2 - At the end : oxygen & co2 are [{:?}, {:?}] so number is [{}]",
+        "PART 2 - At the end : oxygen & co2 are [{}, {}] so number is [{}]",
         oxygen,
         co2,
-        oxygen * co2
+        Day03::part2(&report)
     );
-}
 
-fn extract_binary_rates(frequency_array: [[u16; 2]; 12]) -> (String, String) {
-    let mut gamma = String::with_capacity(12);
-    let mut epsilon = String::with_capacity(12);
-
-    for frequency in frequency_array.iter() {
-        if frequency[0] > frequency[1] {
-            gamma.push('1');
-            epsilon.push('0');
-        } else {
-            gamma.push('0');
-            epsilon.push('1');
-        }
+    for rule in &rules {
+        print_selection(rule, &report);
     }
+}
 
-    (gamma, epsilon)
+fn print_selection(rule: &Rule, report: &Report) {
+    let selection = rule.select(report);
+    let reading = report.reading(selection.reading);
+    println!();
+    println!("RULE {}", rule);
+    for step in &selection.trace {
+        println!(
+            "  bit {:>3} : {} zeros & {} ones, keeps {} : {} eliminated, {} left",
+            step.bit, step.zeros, step.ones, step.kept as u8, step.eliminated, step.remaining
+        );
+    }
+    match reading.to_u128() {
+        Some(value) => println!("  reading #{} [{}] = {}", selection.reading, reading, value),
+        None => println!("  reading #{} [{}]", selection.reading, reading),
+    }
+    if selection.candidates > 1 {
+        println!(
+            "  {} candidates are left, the first one is kept",
+            selection.candidates
+        );
+    }
 }
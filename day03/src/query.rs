@@ -0,0 +1,316 @@
+//! The rating rules, for the oxygen & CO2 ratings of the puzzle as well as any other one, & their language.
+//! One rule per line, `#` starting a comment :
+//!
+//! ```text
+//! oxygen : most tie 1
+//! quiet  : least tie 1 skip 3-5
+//! recent : most last 20
+//! ```
+//!
+//! - `most` or `least` keeps the readings with the most or least common bit, one of them is required,
+//! - `tie 0` or `tie 1` is the bit kept when both are as common, 1 for `most` & 0 for `least` by default,
+//! - `skip 0,3-5` leaves some bits out, bit 0 being the leftmost one,
+//! - `last 20` only counts the bits of the last 20 candidates, in the order of the report.
+//!
+//! Like the puzzle ratings, a rule never eliminates every candidate : when none of them has the chosen
+//! bit, they are all kept. The puzzle ratings are rules too, `oxygen : most tie 1` & `co2 : least tie 0`,
+//! so that every rating goes through the same engine : the candidates of each rule are a bitset, narrowed
+//! down by all the rules together in a single walk over the columns of the report, each count being a
+//! popcount per word.
+
+use std::fmt;
+
+use common::{parse, Result};
+
+use crate::{BitSet, Report};
+
+/// How a rating chooses the bit to keep from how many candidates have a 0 & a 1 there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitCriteria {
+    /// The most common bit, `tie` when both are as common
+    MostCommon { tie: bool },
+    /// The least common bit, `tie` when both are as common
+    LeastCommon { tie: bool },
+}
+
+impl BitCriteria {
+    /// The oxygen generator rating : the most common bit, 1 on a tie
+    pub const OXYGEN: BitCriteria = BitCriteria::MostCommon { tie: true };
+    /// The CO2 scrubber rating : the least common bit, 0 on a tie
+    pub const CO2: BitCriteria = BitCriteria::LeastCommon { tie: false };
+
+    pub fn choose(self, zeros: usize, ones: usize) -> bool {
+        match self {
+            _ if zeros == ones => self.tie(),
+            BitCriteria::MostCommon { .. } => ones > zeros,
+            BitCriteria::LeastCommon { .. } => ones < zeros,
+        }
+    }
+
+    fn tie(self) -> bool {
+        match self {
+            BitCriteria::MostCommon { tie } | BitCriteria::LeastCommon { tie } => tie,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    name: String,
+    criteria: BitCriteria,
+    /// Ranges of bits left out, both ends included
+    skip: Vec<(usize, usize)>,
+    last: Option<usize>,
+}
+
+/// A bit the candidates were narrowed down on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub bit: usize,
+    /// How many of the counted candidates have a 0 & a 1 there
+    pub zeros: usize,
+    pub ones: usize,
+    /// The bit the remaining candidates have there
+    pub kept: bool,
+    pub eliminated: usize,
+    pub remaining: usize,
+}
+
+/// The reading a rule selected & how it got there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    /// The first of the candidates left at the end, in the order of the report
+    pub reading: usize,
+    /// How many candidates are left at the end, more than 1 when skipped bits were the only difference
+    /// between them
+    pub candidates: usize,
+    pub trace: Vec<Step>,
+}
+
+impl Rule {
+    pub fn new(name: &str, criteria: BitCriteria) -> Self {
+        Rule {
+            name: name.to_string(),
+            criteria,
+            skip: vec![],
+            last: None,
+        }
+    }
+
+    /// The oxygen generator rating of the puzzle
+    pub fn oxygen() -> Self {
+        Rule::new("oxygen", BitCriteria::OXYGEN)
+    }
+
+    /// The CO2 scrubber rating of the puzzle
+    pub fn co2() -> Self {
+        Rule::new("co2", BitCriteria::CO2)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn skips(&self, bit: usize) -> bool {
+        self.skip
+            .iter()
+            .any(|(from, to)| (*from..=*to).contains(&bit))
+    }
+
+    /// Narrows the readings of `report` down bit after bit, until one is left or every bit was looked at
+    pub fn select(&self, report: &Report) -> Selection {
+        select_all(report, std::slice::from_ref(self)).remove(0)
+    }
+
+    /// Narrows the candidates of `walk` down on `bit`, whose readings are `column`
+    fn narrow(&self, walk: &mut Walk, bit: usize, column: &BitSet) {
+        let remaining = walk.remaining;
+        let all_ones = walk.candidates.count_ones_and(column);
+        let (zeros, ones) = match self.last {
+            None => (remaining - all_ones, all_ones),
+            Some(last) => {
+                let (counted, ones) = walk.candidates.count_last_ones_and(column, last);
+                (counted - ones, ones)
+            }
+        };
+
+        let mut kept = self.criteria.choose(zeros, ones);
+        if (kept && all_ones == 0) || (!kept && all_ones == remaining) {
+            kept = !kept;
+        }
+        walk.candidates.retain(column, kept);
+        let left = if kept { all_ones } else { remaining - all_ones };
+        walk.trace.push(Step {
+            bit,
+            zeros,
+            ones,
+            kept,
+            eliminated: remaining - left,
+            remaining: left,
+        });
+        walk.remaining = left;
+    }
+}
+
+/// The candidates a rule has left
+struct Walk {
+    candidates: BitSet,
+    remaining: usize,
+    trace: Vec<Step>,
+}
+
+/// The reading each of `rules` selects, all of them narrowing their candidates down together bit after bit
+pub fn select_all(report: &Report, rules: &[Rule]) -> Vec<Selection> {
+    let mut walks: Vec<Walk> = rules
+        .iter()
+        .map(|_| Walk {
+            candidates: BitSet::ones(report.len()),
+            remaining: report.len(),
+            trace: vec![],
+        })
+        .collect();
+
+    for bit in 0..report.width() {
+        if walks.iter().all(|walk| walk.remaining <= 1) {
+            break;
+        }
+        let column = report.column(bit);
+        for (rule, walk) in rules.iter().zip(&mut walks) {
+            if walk.remaining > 1 && !rule.skips(bit) {
+                rule.narrow(walk, bit, column);
+            }
+        }
+    }
+
+    walks
+        .into_iter()
+        .map(|walk| Selection {
+            reading: walk.candidates.first_one().unwrap_or(0),
+            candidates: walk.remaining,
+            trace: walk.trace,
+        })
+        .collect()
+}
+
+/// Writes the rule back in the rules language
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (common, tie) = match self.criteria {
+            BitCriteria::MostCommon { tie } => ("most", tie),
+            BitCriteria::LeastCommon { tie } => ("least", tie),
+        };
+        write!(f, "{} : {} tie {}", self.name, common, tie as u8)?;
+        if !self.skip.is_empty() {
+            let ranges: Vec<String> = self
+                .skip
+                .iter()
+                .map(|(from, to)| {
+                    if from == to {
+                        from.to_string()
+                    } else {
+                        format!("{}-{}", from, to)
+                    }
+                })
+                .collect();
+            write!(f, " skip {}", ranges.join(","))?;
+        }
+        if let Some(last) = self.last {
+            write!(f, " last {}", last)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the rules of `content`, one per line
+pub fn parse_rules(content: &str) -> Result<Vec<Rule>> {
+    let mut rules = vec![];
+    for line in content.split('\n') {
+        let code = line.split('#').next().unwrap_or("").trim();
+        if !code.is_empty() {
+            rules.push(parse_rule(content, code)?);
+        }
+    }
+    Ok(rules)
+}
+
+fn parse_rule(content: &str, code: &str) -> Result<Rule> {
+    let Some((name, spec)) = code.split_once(':') else {
+        return Err(parse::error(
+            content,
+            &code[code.len()..],
+            "`:` after the name of the rule",
+        ));
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(parse::error(content, &code[..0], "the name of the rule"));
+    }
+
+    let mut words = spec.split_whitespace();
+    let mut common = None;
+    let (mut tie, mut skip, mut last) = (None, vec![], None);
+    while let Some(word) = words.next() {
+        match word {
+            "most" | "least" if common.is_none() => common = Some(word),
+            "tie" => {
+                let bit = parse::field(content, code, words.next(), "the bit kept on a tie")?;
+                tie = Some(match bit {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err(parse::error(content, bit, "`0` or `1`")),
+                });
+            }
+            "skip" => {
+                let ranges = parse::field(content, code, words.next(), "bits such as `0,3-5`")?;
+                for range in ranges.split(',') {
+                    let (from, to) = range.split_once('-').unwrap_or((range, range));
+                    let (from, to) = (parse::number(content, from)?, parse::number(content, to)?);
+                    if from > to {
+                        return Err(parse::error(
+                            content,
+                            range,
+                            "a range from low to high bits",
+                        ));
+                    }
+                    skip.push((from, to));
+                }
+            }
+            "last" => {
+                let count = parse::field(content, code, words.next(), "a number of readings")?;
+                last = match parse::number(content, count)? {
+                    0 => return Err(parse::error(content, count, "at least 1 reading")),
+                    n => Some(n),
+                };
+            }
+            _ => {
+                return Err(parse::error(
+                    content,
+                    word,
+                    "one of `most`, `least`, `tie`, `skip` or `last`",
+                ))
+            }
+        }
+    }
+
+    let criteria = match common {
+        Some("most") => BitCriteria::MostCommon {
+            tie: tie.unwrap_or(true),
+        },
+        Some(_) => BitCriteria::LeastCommon {
+            tie: tie.unwrap_or(false),
+        },
+        None => {
+            return Err(parse::error(
+                content,
+                &code[code.len()..],
+                "`most` or `least`",
+            ))
+        }
+    };
+    Ok(Rule {
+        name: name.to_string(),
+        criteria,
+        skip,
+        last,
+    })
+}
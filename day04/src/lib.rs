@@ -0,0 +1,551 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use common::{parse, Answer, Result, Solver};
+use grid::Point;
+
+mod timeline;
+
+pub use timeline::{Entry, Timeline};
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Bingo;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        Bingo::new(content)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input
+            .clone()
+            .first_winner()
+            .map_or(Answer::Unsolved, |(sum, number)| {
+                (sum * number as u64).into()
+            })
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input
+            .clone()
+            .last_winner_score()
+            .map_or(Answer::Unsolved, Answer::from)
+    }
+}
+
+/// The kinds of lines a grid wins with
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Pattern {
+    Rows,
+    Columns,
+    /// Both diagonals, only on square grids
+    Diagonals,
+    /// The 4 corners of the grid
+    Corners,
+    /// Every number of the grid
+    FullCard,
+}
+
+impl Pattern {
+    /// The patterns of the puzzle
+    pub const DEFAULT: [Pattern; 2] = [Pattern::Rows, Pattern::Columns];
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Pattern::Rows => "rows",
+            Pattern::Columns => "columns",
+            Pattern::Diagonals => "diagonals",
+            Pattern::Corners => "corners",
+            Pattern::FullCard => "full",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "rows" => Ok(Pattern::Rows),
+            "columns" => Ok(Pattern::Columns),
+            "diagonals" => Ok(Pattern::Diagonals),
+            "corners" => Ok(Pattern::Corners),
+            "full" => Ok(Pattern::FullCard),
+            _ => Err(format!("unknown win pattern `{}`", s)),
+        }
+    }
+}
+
+/// The line a grid won with
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Line {
+    Row(usize),
+    Column(usize),
+    /// From the top left corner to the bottom right one
+    Diagonal,
+    /// From the top right corner to the bottom left one
+    AntiDiagonal,
+    Corners,
+    FullCard,
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Line::Row(row) => format!("row {}", row),
+            Line::Column(column) => format!("column {}", column),
+            Line::Diagonal => "diagonal".to_string(),
+            Line::AntiDiagonal => "anti-diagonal".to_string(),
+            Line::Corners => "corners".to_string(),
+            Line::FullCard => "full card".to_string(),
+        };
+        f.pad(&name)
+    }
+}
+
+/// A grid & how many of its numbers are marked on each line it can win with, so that a win is spotted
+/// from the counter of the lines going through the marked cell alone
+#[derive(Debug, Clone)]
+pub struct Grid {
+    marked: grid::Grid<bool>,
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+    /// The diagonal & the anti-diagonal, only counted on square grids
+    diagonals: [usize; 2],
+    corners: usize,
+    count: usize,
+    unmarked: u64,
+}
+
+impl Grid {
+    fn new(width: usize, height: usize) -> Self {
+        Grid {
+            marked: grid::Grid::new(width, height, false),
+            rows: vec![0; height],
+            columns: vec![0; width],
+            diagonals: [0, 0],
+            corners: 0,
+            count: 0,
+            unmarked: 0,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.marked.width()
+    }
+
+    fn height(&self) -> usize {
+        self.marked.height()
+    }
+
+    fn is_corner(&self, cell: Point) -> bool {
+        (cell.x == 0 || cell.x == self.width() - 1) && (cell.y == 0 || cell.y == self.height() - 1)
+    }
+
+    /// How many distinct corners the grid has, fewer than 4 when it is a single row or column
+    fn corner_count(&self) -> usize {
+        self.width().min(2) * self.height().min(2)
+    }
+
+    /// Marks `value` at `cell`, returns the line of `patterns` it completes. A cell already marked is left
+    /// as it is.
+    fn mark(&mut self, cell: Point, value: u16, patterns: &[Pattern]) -> Option<Line> {
+        if self.marked[cell] {
+            return None;
+        }
+        self.marked[cell] = true;
+        self.unmarked -= value as u64;
+        self.count += 1;
+        self.rows[cell.y] += 1;
+        self.columns[cell.x] += 1;
+
+        let (width, height) = (self.width(), self.height());
+        let square = width == height;
+        let on_diagonal = square && cell.x == cell.y;
+        let on_anti_diagonal = square && cell.x + cell.y == width - 1;
+        if on_diagonal {
+            self.diagonals[0] += 1;
+        }
+        if on_anti_diagonal {
+            self.diagonals[1] += 1;
+        }
+        let is_corner = self.is_corner(cell);
+        if is_corner {
+            self.corners += 1;
+        }
+
+        patterns.iter().find_map(|pattern| match pattern {
+            Pattern::Rows if self.rows[cell.y] == width => Some(Line::Row(cell.y)),
+            Pattern::Columns if self.columns[cell.x] == height => Some(Line::Column(cell.x)),
+            Pattern::Diagonals if on_diagonal && self.diagonals[0] == width => Some(Line::Diagonal),
+            Pattern::Diagonals if on_anti_diagonal && self.diagonals[1] == width => {
+                Some(Line::AntiDiagonal)
+            }
+            Pattern::Corners if is_corner && self.corners == self.corner_count() => {
+                Some(Line::Corners)
+            }
+            Pattern::FullCard if self.count == width * height => Some(Line::FullCard),
+            _ => None,
+        })
+    }
+}
+
+/// A grid completing a line
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Win {
+    /// The grid, in the order of the input
+    pub grid: usize,
+    /// The draw, in the order of the numbers
+    pub draw: usize,
+    pub number: u16,
+    pub line: Line,
+    /// The sum of the numbers of the grid that aren't marked
+    pub unmarked: u64,
+}
+
+impl Win {
+    pub fn score(&self) -> u64 {
+        self.unmarked * self.number as u64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bingo {
+    numbers: Vec<u16>,
+    grids: Vec<Grid>,
+    /// The grids & cells of each number
+    index: HashMap<u16, Vec<(usize, Point)>>,
+    patterns: Vec<Pattern>,
+}
+
+impl Bingo {
+    /// Parses the numbers then the grids, which all have the size of the first one
+    pub fn new(content: &str) -> Result<Self> {
+        let mut lines = content.split('\n');
+
+        // First the numbers
+        let numbers = lines
+            .next()
+            .unwrap_or("")
+            .split(',')
+            .map(|x| parse::number(content, x))
+            .collect::<Result<_>>()?;
+
+        // Then the grids, separated by empty lines
+        let mut grids: Vec<Grid> = vec![];
+        let mut index = HashMap::new();
+        let mut size = None;
+        let mut rows: Vec<&str> = vec![];
+        let mut separated = false;
+        for line in lines {
+            if line.is_empty() {
+                if !rows.is_empty() {
+                    grids.push(parse_grid(
+                        content,
+                        &rows,
+                        &mut size,
+                        grids.len(),
+                        &mut index,
+                    )?);
+                    rows.clear();
+                }
+                separated = true;
+            } else if !separated {
+                return Err(parse::error(
+                    content,
+                    line,
+                    "an empty line between the numbers & the grids",
+                ));
+            } else {
+                rows.push(line);
+            }
+        }
+        if !rows.is_empty() {
+            grids.push(parse_grid(
+                content,
+                &rows,
+                &mut size,
+                grids.len(),
+                &mut index,
+            )?);
+        }
+
+        Ok(Bingo {
+            numbers,
+            grids,
+            index,
+            patterns: Pattern::DEFAULT.to_vec(),
+        })
+    }
+
+    /// The same game, won with any line of `patterns` instead of rows & columns
+    pub fn with_patterns(mut self, patterns: &[Pattern]) -> Self {
+        self.patterns = patterns.to_vec();
+        self
+    }
+
+    /// Draws every number, returns the grids that won in the order they did. Only the cells of the drawn
+    /// numbers are looked at, so that a game takes a time proportional to the draws & the marks.
+    pub fn play(mut self) -> Vec<Win> {
+        self.draw_all()
+    }
+
+    /// Plays the whole game, for every grid
+    pub fn timeline(mut self) -> Timeline {
+        let wins = self.draw_all();
+        Timeline::new(wins, self.grids.iter().map(|grid| grid.unmarked).collect())
+    }
+
+    fn draw_all(&mut self) -> Vec<Win> {
+        let mut wins = vec![];
+        let mut won = vec![false; self.grids.len()];
+        for (draw, number) in self.numbers.iter().copied().enumerate() {
+            // A grid having the number several times wins once they are all marked
+            let mut winners: Vec<(usize, Line)> = vec![];
+            for &(i, cell) in self.index.get(&number).into_iter().flatten() {
+                if won[i] {
+                    continue;
+                }
+                let line = self.grids[i].mark(cell, number, &self.patterns);
+                if let Some(line) = line.filter(|_| winners.iter().all(|(j, _)| *j != i)) {
+                    winners.push((i, line));
+                }
+            }
+            for (i, line) in winners {
+                won[i] = true;
+                wins.push(Win {
+                    grid: i,
+                    draw,
+                    number,
+                    line,
+                    unmarked: self.grids[i].unmarked,
+                });
+            }
+        }
+        wins
+    }
+
+    /// Part 1: Check grids until first complete grid, returns its unmarked sum & the last number drawn
+    pub fn first_winner(self) -> Option<(u64, u16)> {
+        let win = *self.play().first()?;
+        Some((win.unmarked, win.number))
+    }
+
+    /// Part 2: Check grids until end of numbers, then retrieve last
+    pub fn last_winner_score(self) -> Option<u64> {
+        Some(self.play().last()?.score())
+    }
+}
+
+/// Parses the `rows` of grid `id`, which must have the `(width, height)` of the previous grids : the first
+/// grid sets it. Its numbers are added to `index`.
+fn parse_grid(
+    content: &str,
+    rows: &[&str],
+    size: &mut Option<(usize, usize)>,
+    id: usize,
+    index: &mut HashMap<u16, Vec<(usize, Point)>>,
+) -> Result<Grid> {
+    let width = size.map_or_else(|| rows[0].split_whitespace().count(), |(width, _)| width);
+    let height = size.map_or(rows.len(), |(_, height)| height);
+    *size = Some((width, height));
+    if width == 0 {
+        return Err(parse::error(content, rows[0], "a row of numbers"));
+    }
+
+    if let Some(extra) = rows.get(height) {
+        return Err(parse::error(
+            content,
+            extra,
+            format!("an empty line after the {} rows of a grid", height),
+        ));
+    }
+    if rows.len() < height {
+        let last = rows[rows.len() - 1];
+        return Err(parse::error(
+            content,
+            &last[last.len()..],
+            format!("{} rows per grid", height),
+        ));
+    }
+
+    let mut grid = Grid::new(width, height);
+    for (row_number, row) in rows.iter().enumerate() {
+        let mut col_count = 0;
+        for (col, number_str) in row.split_whitespace().enumerate() {
+            if col == width {
+                return Err(parse::error(
+                    content,
+                    number_str,
+                    format!("{} numbers per row", width),
+                ));
+            }
+            let number: u16 = parse::number(content, number_str)?;
+            grid.unmarked += number as u64;
+            index
+                .entry(number)
+                .or_default()
+                .push((id, Point::new(col, row_number)));
+            col_count += 1;
+        }
+        if col_count < width {
+            let end_of_line = &row[row.len()..];
+            return Err(parse::error(
+                content,
+                end_of_line,
+                format!("{} numbers per row", width),
+            ));
+        }
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bingo, Day04, Line, Pattern, Win};
+    use common::parse::assert_parse_error_at;
+    use common::{Answer, Solver};
+
+    const CONTENT: &str = "5,1,9,3,7,2
+
+1 2 3
+4 5 6
+7 8 9
+
+9 8 7
+6 5 4
+3 2 1";
+
+    fn lines(patterns: &[Pattern]) -> Vec<(usize, usize, Line)> {
+        Bingo::new(CONTENT)
+            .unwrap()
+            .with_patterns(patterns)
+            .play()
+            .iter()
+            .map(|win| (win.grid, win.draw, win.line))
+            .collect()
+    }
+
+    #[test]
+    fn it_plays_grids_of_any_size() {
+        let wins = Bingo::new(CONTENT).unwrap().play();
+        assert_eq!(
+            wins,
+            [
+                Win {
+                    grid: 0,
+                    draw: 5,
+                    number: 2,
+                    line: Line::Row(0),
+                    unmarked: 4 + 6 + 8
+                },
+                Win {
+                    grid: 1,
+                    draw: 5,
+                    number: 2,
+                    line: Line::Row(2),
+                    unmarked: 8 + 6 + 4
+                }
+            ]
+        );
+
+        assert_eq!(
+            lines(&[Pattern::Diagonals]),
+            [(0, 2, Line::Diagonal), (1, 2, Line::Diagonal)]
+        );
+        assert_eq!(
+            lines(&[Pattern::Corners, Pattern::Columns]),
+            [(0, 4, Line::Corners), (1, 4, Line::Corners)]
+        );
+        assert_eq!(lines(&[Pattern::FullCard]), []);
+
+        let wide = Bingo::new("1,4,2,3\n\n1 2 3 4\n5 6 7 8").unwrap();
+        let wins = wide
+            .with_patterns(&[Pattern::Diagonals, Pattern::Corners])
+            .play();
+        assert_eq!(wins.len(), 0);
+    }
+
+    #[test]
+    fn it_scores_without_overflowing() {
+        let bingo = Bingo::new("7,7,65000,7\n\n65000 7\n60000 7\n\n7 7\n7 1").unwrap();
+        let wins = bingo.with_patterns(&[Pattern::Rows]).play();
+        // Drawing 7 twice marks it once, & marks it everywhere on the second grid before it wins
+        assert_eq!(
+            wins.iter()
+                .map(|win| (win.grid, win.draw, win.line, win.score()))
+                .collect::<Vec<_>>(),
+            [(1, 0, Line::Row(0), 7), (0, 2, Line::Row(0), 60000 * 65000)]
+        );
+    }
+
+    #[test]
+    fn it_leaves_games_without_winner_unsolved() {
+        for content in ["1,4\n\n1 2\n3 4", "1,4"] {
+            let bingo = Day04::parse(content).unwrap();
+            assert_eq!(Day04::part1(&bingo), Answer::Unsolved, "{}", content);
+            assert_eq!(Day04::part2(&bingo), Answer::Unsolved, "{}", content);
+        }
+    }
+
+    #[test]
+    fn it_ranks_the_grids_winning_together_equally() {
+        let content = format!(
+            "{}\n\n3 7 4\n20 21 22\n23 24 25",
+            CONTENT.replace("5,1,9,3,7,2", "5,1,9,3,7,2,4")
+        );
+        let timeline = Bingo::new(&content).unwrap().timeline();
+        assert_eq!(
+            timeline
+                .entries()
+                .iter()
+                .map(|entry| (entry.grid, entry.rank))
+                .collect::<Vec<_>>(),
+            [(0, Some(1)), (1, Some(1)), (2, Some(3))]
+        );
+        assert!(timeline.to_table().contains("\n    3 |    2 |    6 |"));
+    }
+
+    #[test]
+    fn it_reports_the_whole_game() {
+        let timeline = Bingo::new("5,1,9\n\n1 2\n3 4\n\n5 1\n6 7")
+            .unwrap()
+            .timeline();
+        assert_eq!(
+            timeline.to_table(),
+            " Rank | Grid | Draw | Number | Line          | Unmarked | Score
+------+------+------+--------+---------------+----------+-------
+    1 |    1 |    1 |      1 | row 0         |       13 | 13
+    - |    0 |    - |      - | never won     |        9 | -
+"
+        );
+        assert_eq!(
+            timeline.to_json(),
+            r#"[
+  {"rank": 1, "grid": 1, "draw": 1, "number": 1, "line": "row 0", "unmarked": 13, "score": 13},
+  {"rank": null, "grid": 0, "draw": null, "number": null, "line": null, "unmarked": 9, "score": null}
+]
+"#
+        );
+    }
+
+    #[test]
+    fn it_checks_every_grid_has_the_size_of_the_first() {
+        for (content, position) in [
+            ("1,2\n\n1 2\n3 4\n\n1 2 3\n4 5", (6, 5)),
+            ("1,2\n\n1 2\n3 4\n\n1 2\n3", (7, 2)),
+            ("1,2\n\n1 2\n3 4\n\n1 2\n3 4\n5 6", (8, 1)),
+            ("1,2\n\n1 2\n3 4\n\n1 2", (6, 4)),
+            ("1,2\n1 2\n3 4", (2, 1)),
+        ] {
+            assert_parse_error_at(Bingo::new(content), position);
+        }
+    }
+}
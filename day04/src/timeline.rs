@@ -0,0 +1,119 @@
+//! The whole game at once : when each grid won, with which line & for how much, so that every grid can be
+//! checked & not only the first & last winners of the puzzle.
+
+use std::fmt::Write;
+
+use crate::{Line, Win};
+
+/// Every grid at the end of the game, the winners first in the order they won, then the other ones in the
+/// order of the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeline {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// When the grid won among the winners, from 1 : the grids winning on the same draw share a rank &
+    /// the next ones are ranked as if they hadn't. `None` when the grid never won.
+    pub rank: Option<usize>,
+    pub grid: usize,
+    /// `None` when the grid never won
+    pub win: Option<Win>,
+    /// The sum of the numbers of the grid that aren't marked, when it won or at the end of the game
+    pub unmarked: u64,
+}
+
+impl Timeline {
+    pub(crate) fn new(wins: Vec<Win>, unmarked: Vec<u64>) -> Self {
+        // The wins are in the order of the draws
+        let mut entries: Vec<Entry> = vec![];
+        for (i, win) in wins.iter().enumerate() {
+            let rank = match entries.last() {
+                Some(Entry {
+                    rank,
+                    win: Some(previous),
+                    ..
+                }) if previous.draw == win.draw => *rank,
+                _ => Some(i + 1),
+            };
+            entries.push(Entry {
+                rank,
+                grid: win.grid,
+                win: Some(*win),
+                unmarked: win.unmarked,
+            });
+        }
+        let mut won = vec![false; unmarked.len()];
+        for win in &wins {
+            won[win.grid] = true;
+        }
+        for (grid, unmarked) in unmarked.into_iter().enumerate() {
+            if !won[grid] {
+                entries.push(Entry {
+                    rank: None,
+                    grid,
+                    win: None,
+                    unmarked,
+                });
+            }
+        }
+        Timeline { entries }
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// One row per grid, ranked by when it won. Grids & draws are numbered from 0, in the order of the input.
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            " Rank | Grid | Draw | Number | {:<13} | Unmarked | Score\n",
+            "Line"
+        );
+        table.push_str("------+------+------+--------+---------------+----------+-------\n");
+        for entry in &self.entries {
+            let cells = win_cells(entry, |line| line.to_string())
+                .unwrap_or_else(|| ["-", "-", "-", "never won", "-"].map(String::from));
+            let [rank, draw, number, line, score] = cells;
+            writeln!(
+                table,
+                " {:>4} | {:>4} | {:>4} | {:>6} | {:<13} | {:>8} | {}",
+                rank, entry.grid, draw, number, line, entry.unmarked, score
+            )
+            .unwrap();
+        }
+        table
+    }
+
+    /// One `{rank, grid, draw, number, line, unmarked, score}` record per grid, in the order of the table,
+    /// the grids that never won having `null` ranks, draws, numbers, lines & scores
+    pub fn to_json(&self) -> String {
+        let records: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let [rank, draw, number, line, score] =
+                    win_cells(entry, |line| format!("\"{}\"", line))
+                        .unwrap_or_else(|| ["null"; 5].map(String::from));
+                format!(
+                    "{{\"rank\": {}, \"grid\": {}, \"draw\": {}, \"number\": {}, \"line\": {}, \"unmarked\": {}, \"score\": {}}}",
+                    rank, entry.grid, draw, number, line, entry.unmarked, score
+                )
+            })
+            .collect();
+        format!("[\n  {}\n]\n", records.join(",\n  "))
+    }
+}
+
+/// The rank, draw, number, line & score of the entry when its grid won, the line written by `line`
+fn win_cells(entry: &Entry, line: impl Fn(Line) -> String) -> Option<[String; 5]> {
+    let (rank, win) = (entry.rank?, entry.win?);
+    Some([
+        rank.to_string(),
+        win.draw.to_string(),
+        win.number.to_string(),
+        line(win.line),
+        win.score().to_string(),
+    ])
+}
@@ -0,0 +1,216 @@
+use std::fmt;
+
+use common::{parse, Answer, Result, Solver};
+use grid::Point;
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Vec<Segment>;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_segments(content)
+    }
+
+    /// Fill Grid using segmentList and NOT counting diagonal lines
+    fn part1(input: &Self::Input) -> Answer {
+        fill_grid(input, false).dangerous_points_count().into()
+    }
+
+    /// Fill Grid using segmentList and counting diagonal lines
+    fn part2(input: &Self::Input) -> Answer {
+        fill_grid(input, true).dangerous_points_count().into()
+    }
+}
+
+/// How many lines of vents cover each point of the ocean floor
+#[derive(Debug, Clone)]
+pub struct Grid {
+    counts: grid::Grid<u16>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Coordinate {
+    row: u16,
+    column: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct Segment {
+    start: Coordinate,
+    end: Coordinate,
+}
+
+impl Segment {
+    fn points(&self, counting_diagonals: bool) -> Vec<Coordinate> {
+        let mut p = vec![];
+        if self.start.row == self.end.row {
+            let range = if self.start.column <= self.end.column {
+                self.start.column..=self.end.column
+            } else {
+                self.end.column..=self.start.column
+            };
+            // Rows are same => lets compute points by using columns between
+            for c in range {
+                p.push(Coordinate {
+                    row: self.start.row,
+                    column: c,
+                });
+            }
+        }
+        if self.start.column == self.end.column {
+            let range = if self.start.row <= self.end.row {
+                self.start.row..=self.end.row
+            } else {
+                self.end.row..=self.start.row
+            };
+            // Columns are same => lets compute points by using rows between
+            for r in range {
+                p.push(Coordinate {
+                    row: r,
+                    column: self.start.column,
+                });
+            }
+        }
+
+        if counting_diagonals && self.is_diagonal_45() {
+            // We are on a 45 diagnonal
+
+            // Determine ranges
+            let row_range = if self.start.row <= self.end.row {
+                self.start.row..=self.end.row
+            } else {
+                self.end.row..=self.start.row
+            };
+            let col_range = if self.start.column <= self.end.column {
+                self.start.column..=self.end.column
+            } else {
+                self.end.column..=self.start.column
+            };
+
+            if self.start.row + self.start.column != self.end.row + self.end.column {
+                let min_col_range = *col_range.start();
+                for (i, r) in row_range.enumerate() {
+                    p.push(Coordinate {
+                        row: r,
+                        column: min_col_range + i as u16,
+                    });
+                }
+            } else {
+                // self.start.row + self.start.column == self.end.row + self.end.column
+                let max_col_range = *col_range.end();
+
+                for (i, r) in row_range.enumerate() {
+                    p.push(Coordinate {
+                        row: r,
+                        column: max_col_range - i as u16,
+                    });
+                }
+            }
+        }
+
+        p
+    }
+
+    fn is_diagonal_45(&self) -> bool {
+        self.end.row.abs_diff(self.start.row) == self.end.column.abs_diff(self.start.column)
+    }
+}
+
+impl Grid {
+    /// An ocean floor with no vents, large enough for every point up to `max`
+    pub fn new(max: &Coordinate) -> Self {
+        Grid {
+            counts: grid::Grid::new(max.row as usize + 1, max.column as usize + 1, 0),
+        }
+    }
+
+    fn add_point(&mut self, coord: Coordinate) {
+        self.counts[Point::new(coord.row as usize, coord.column as usize)] += 1;
+    }
+
+    pub fn dangerous_points_count(&mut self) -> u16 {
+        self.counts.iter().filter(|(_, x)| **x >= 2u16).count() as u16
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let grid = self.counts.map(|count| match count {
+            0 => ".".to_string(),
+            count => count.to_string(),
+        });
+        write!(f, "{}", grid)
+    }
+}
+
+pub fn parse_segments(content: &str) -> Result<Vec<Segment>> {
+    let mut segment_list: Vec<Segment> = vec![];
+
+    // Parse input & prepare SegmentList
+    for input_line in content.split('\n') {
+        let mut iter = input_line.split(" -> ");
+
+        // Parse before "->" : the source
+        let source = parse::field(content, input_line, iter.next(), "a source `x,y`")?;
+        let source_coord = parse_coordinate(content, source)?;
+
+        // Parse after "->" : the destination
+        let dest = parse::field(
+            content,
+            input_line,
+            iter.next(),
+            "` -> ` & a destination `x,y`",
+        )?;
+        let dest_coord = parse_coordinate(content, dest)?;
+
+        segment_list.push(Segment {
+            start: source_coord,
+            end: dest_coord,
+        });
+    }
+
+    Ok(segment_list)
+}
+
+/// Parses a `x,y` coordinate
+fn parse_coordinate(content: &str, text: &str) -> Result<Coordinate> {
+    let mut iter = text.split(',');
+    let x = parse::field(content, text, iter.next(), "a `x` coordinate")?;
+    let y = parse::field(content, text, iter.next(), "`,` & a `y` coordinate")?;
+    if let Some(extra) = iter.next() {
+        return Err(parse::error(content, extra, "only `x,y`"));
+    }
+    Ok(Coordinate {
+        row: parse::number(content, x)?,
+        column: parse::number(content, y)?,
+    })
+}
+
+/// Fill a Grid using segment list, counting diagonal lines or not
+pub fn fill_grid(segment_list: &[Segment], counting_diagonals: bool) -> Grid {
+    let max = Coordinate {
+        row: segment_list
+            .iter()
+            .map(|s| s.start.row.max(s.end.row))
+            .max()
+            .unwrap_or(0),
+        column: segment_list
+            .iter()
+            .map(|s| s.start.column.max(s.end.column))
+            .max()
+            .unwrap_or(0),
+    };
+    let mut grid = Grid::new(&max);
+    for segment in segment_list {
+        segment
+            .points(counting_diagonals)
+            .into_iter()
+            .for_each(|p| {
+                grid.add_point(p);
+            });
+    }
+    grid
+}
@@ -0,0 +1,158 @@
+use common::{parse, Answer, Result, Solver};
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    const DAY: u8 = 6;
+
+    /// Timer of every fish of the first batch
+    type Input = Vec<u16>;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        content
+            .split(',')
+            .map(|x| match parse::number(content, x)? {
+                timer @ 0..=8 => Ok(timer),
+                _ => Err(parse::error(content, x, "a timer between 0 and 8")),
+            })
+            .collect()
+    }
+
+    /// Simple algorithm
+    fn part1(input: &Self::Input) -> Answer {
+        let mut sea = Sea::new(input);
+        sea.spend_n_days(80);
+        sea.fish_count().into()
+    }
+
+    /// Simple algorithm doesn't work, optimize by applying rotate.
+    fn part2(input: &Self::Input) -> Answer {
+        let mut sea = SeaPart2::new(input);
+        sea.spend_n_days(256);
+        sea.fish_count().into()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Fish {
+    timer: u16,
+}
+
+impl Fish {
+    fn new(initial_timer: Option<u16>) -> Self {
+        Fish {
+            timer: initial_timer.unwrap_or(8),
+        }
+    }
+
+    fn live_for_a_day_and_say_if_a_new_fish_is_born(&mut self) -> bool {
+        let mut should_create_fish: bool = false;
+        if self.timer == 0 {
+            self.timer = 6;
+            should_create_fish = true;
+        } else {
+            self.timer -= 1;
+        }
+        should_create_fish
+    }
+}
+
+impl From<Fish> for String {
+    fn from(fish: Fish) -> Self {
+        fish.timer.to_string()
+    }
+}
+
+type FishBatch = Vec<Fish>;
+
+#[derive(Debug, Clone)]
+pub struct Sea {
+    fishes: Vec<FishBatch>,
+}
+
+impl Sea {
+    pub fn new(first_batch_timers: &[u16]) -> Self {
+        let mut first_batch: FishBatch = vec![];
+        for fish_timer in first_batch_timers {
+            first_batch.push(Fish::new(Some(*fish_timer)));
+        }
+        Sea {
+            fishes: vec![first_batch],
+        }
+    }
+
+    fn spend_one_day(&mut self) {
+        let mut fishes_born_in_day: FishBatch = vec![];
+
+        for batch in &mut self.fishes {
+            for fish in batch.iter_mut() {
+                if fish.live_for_a_day_and_say_if_a_new_fish_is_born() {
+                    fishes_born_in_day.push(Fish::new(None));
+                }
+            }
+        }
+
+        self.fishes.push(fishes_born_in_day);
+    }
+
+    pub fn spend_n_days(&mut self, number_of_days: u16) {
+        for _day_number in 1..number_of_days + 1 {
+            self.spend_one_day();
+        }
+    }
+
+    pub fn fish_count(&self) -> usize {
+        let mut count = 0;
+        for batch in &self.fishes {
+            count += batch.len();
+        }
+        count
+    }
+}
+
+type FishCountByAge = Vec<usize>;
+
+#[derive(Debug, Clone)]
+pub struct SeaPart2 {
+    fishes: FishCountByAge,
+}
+
+impl SeaPart2 {
+    pub fn new(first_batch_timers: &[u16]) -> Self {
+        let mut first_generation: Vec<u16> = first_batch_timers.to_vec();
+
+        // We add every possible age
+        first_generation.append(&mut [0, 1, 2, 3, 4, 5, 6, 7, 8].to_vec());
+
+        first_generation.sort();
+
+        let fish_count_by_age: FishCountByAge = first_generation
+            .chunk_by(|a, b| a == b)
+            .map(|x| x.len() - 1) //We remove 1 because we added 1 to count also non present ages
+            .collect();
+
+        SeaPart2 {
+            fishes: fish_count_by_age,
+        }
+    }
+
+    fn spend_one_day(&mut self) {
+        let zero_day_fish = self.fishes[0];
+        // We reduce age of every fish by 1
+        self.fishes.rotate_left(1);
+        // Breeding fishes have now the age of 6
+        self.fishes[6] += zero_day_fish;
+        // Born fishes have now the age to 8
+        self.fishes[8] = zero_day_fish;
+    }
+
+    pub fn spend_n_days(&mut self, number_of_days: u16) {
+        for _day_number in 1..number_of_days + 1 {
+            self.spend_one_day();
+        }
+    }
+
+    pub fn fish_count(&self) -> usize {
+        self.fishes.iter().sum()
+    }
+}
@@ -0,0 +1,75 @@
+use common::{parse, Answer, Result, Solver};
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = CrabsArmy;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        CrabsArmy::new(content)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.least_fuel_to_align(DayPart::Part1).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.least_fuel_to_align(DayPart::Part2).into()
+    }
+}
+
+#[derive(Debug)]
+pub struct CrabsArmy {
+    positions: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub enum DayPart {
+    Part1,
+    Part2,
+}
+
+impl CrabsArmy {
+    pub fn new(positions_str: &str) -> Result<Self> {
+        let start_positions: Vec<usize> = positions_str
+            .split(',')
+            .map(|x| parse::number(positions_str, x))
+            .collect::<Result<_>>()?;
+
+        Ok(CrabsArmy {
+            positions: start_positions,
+        })
+    }
+
+    fn min_position(&self) -> usize {
+        *self.positions.iter().min().unwrap()
+    }
+
+    fn max_position(&self) -> usize {
+        *self.positions.iter().max().unwrap()
+    }
+
+    pub fn least_fuel_to_align(&self, daypart: DayPart) -> usize {
+        let mut army_lowest_required_fuel = usize::MAX;
+        for candidate_pos in self.min_position()..=self.max_position() {
+            let army_candidate_required_fuel =
+                self.positions
+                    .iter()
+                    .fold(0usize, |sum, current| match daypart {
+                        DayPart::Part1 => sum + current.abs_diff(candidate_pos),
+                        DayPart::Part2 => {
+                            let diff = current.abs_diff(candidate_pos);
+                            let cost = (1usize..=diff).sum::<usize>();
+                            sum + cost
+                        }
+                    });
+
+            if army_candidate_required_fuel < army_lowest_required_fuel {
+                army_lowest_required_fuel = army_candidate_required_fuel;
+            }
+        }
+        army_lowest_required_fuel
+    }
+}
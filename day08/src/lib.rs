@@ -0,0 +1,294 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use common::{parse, Answer, Result, Solver};
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Notes;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_notes(content)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_easy_digits(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.iter().map(decode_output_value).sum::<usize>().into()
+    }
+}
+
+type Wire = char;
+
+#[derive(Debug, Clone)]
+pub struct SignalPattern {
+    active_wires: Vec<Wire>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DisplayDigit {
+    active_wires: Vec<Wire>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NoteEntry {
+    signal_patterns: [SignalPattern; 10],
+    output_value: [DisplayDigit; 4],
+}
+
+impl SignalPattern {
+    fn new(wires: String) -> Self {
+        Self {
+            active_wires: wires.chars().collect(),
+        }
+    }
+
+    fn is_missing_one_char_from(&self, other: &SignalPattern) -> bool {
+        other
+            .active_wires
+            .iter()
+            .filter(|item| !self.active_wires.contains(item))
+            .count()
+            == 1
+    }
+
+    fn contains_all_wires_of(&self, other: &SignalPattern) -> bool {
+        let mut res = true;
+        for wire in &self.active_wires {
+            if !&other.active_wires.contains(wire) {
+                res = false;
+                break;
+            }
+        }
+        res
+    }
+}
+
+impl fmt::Display for SignalPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.active_wires.iter().collect::<String>())
+    }
+}
+
+impl fmt::Display for DisplayDigit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.active_wires.iter().collect::<String>())
+    }
+}
+
+impl DisplayDigit {
+    fn new(wires: String) -> Self {
+        Self {
+            active_wires: wires.chars().collect(),
+        }
+    }
+
+    fn is_easy(&self) -> bool {
+        matches!(self.active_wires.len(), 2 | 3 | 4 | 7)
+    }
+}
+
+impl NoteEntry {
+    /// Parses the ten signal patterns & the four output digits of `entry`, a line of `content`
+    fn parse(content: &str, entry: &str) -> Result<Self> {
+        let mut iter = entry.split('|');
+        let signals_iter = parse::field(content, entry, iter.next(), "signal patterns")?;
+        let outputs_iter = parse::field(content, entry, iter.next(), "`|` & the output value")?;
+
+        let signals: Vec<SignalPattern> = signals_iter
+            .trim_end()
+            .split(' ')
+            .map(|x| Ok(SignalPattern::new(parse_wires(content, x)?)))
+            .collect::<Result<_>>()?;
+
+        let outputs: Vec<DisplayDigit> = outputs_iter
+            .trim_start()
+            .split(' ')
+            .map(|x| Ok(DisplayDigit::new(parse_wires(content, x)?)))
+            .collect::<Result<_>>()?;
+
+        Ok(NoteEntry {
+            signal_patterns: signals.try_into().map_err(|v: Vec<_>| {
+                parse::error(
+                    content,
+                    signals_iter,
+                    format!("10 signal patterns, not {}", v.len()),
+                )
+            })?,
+            output_value: outputs.try_into().map_err(|v: Vec<_>| {
+                parse::error(
+                    content,
+                    outputs_iter,
+                    format!("4 output digits, not {}", v.len()),
+                )
+            })?,
+        })
+    }
+}
+
+/// Checks that `wires` of `content` only names wires from `a` to `g`
+fn parse_wires(content: &str, wires: &str) -> Result<String> {
+    if wires.is_empty() || !wires.chars().all(|c| ('a'..='g').contains(&c)) {
+        return Err(parse::error(content, wires, "wires from `a` to `g`"));
+    }
+    Ok(wires.to_string())
+}
+
+fn length_to_digit(len: usize) -> Option<usize> {
+    match len {
+        2 => Some(1),
+        3 => Some(7),
+        4 => Some(4),
+        7 => Some(8),
+        _ => None,
+    }
+}
+
+pub type Notes = Vec<NoteEntry>;
+
+pub fn parse_notes(content: &str) -> Result<Notes> {
+    content
+        .split('\n')
+        .map(|entry| NoteEntry::parse(content, entry))
+        .collect()
+}
+
+/// Part 1 : count digits in output values that use a unique number of segments
+pub fn count_easy_digits(notes: &[NoteEntry]) -> usize {
+    let mut easy_count = 0;
+    notes.iter().for_each(|note_entry| {
+        easy_count += note_entry
+            .output_value
+            .iter()
+            .filter(|digit| digit.is_easy())
+            .count();
+    });
+    easy_count
+}
+
+/// Part 2 : deduce every signal pattern of the entry & decode its 4 digit output value
+pub fn decode_output_value(note_entry: &NoteEntry) -> usize {
+    let mut deduced: HashMap<usize, &SignalPattern> = HashMap::new();
+    let mut decoded_digits: Vec<String> = vec![];
+
+    // Identify digit 1, 7, 4 and 8 using unique length
+    note_entry.signal_patterns.iter().for_each(|signal| {
+        if let Some(digit) = length_to_digit(signal.active_wires.len()) {
+            deduced.insert(digit, signal);
+        }
+    });
+
+    // Identify digits with length 6
+
+    // If length is 6 and there is one char diff from signals for digit 1
+    // => Then it is digit 6
+    note_entry.signal_patterns.iter().for_each(|signal| {
+        if signal.active_wires.len() == 6
+            && deduced.contains_key(&1)
+            && signal.is_missing_one_char_from(deduced.get(&1).unwrap())
+        {
+            deduced.insert(6, signal);
+        }
+    });
+
+    // If length is 6 and there is one char diff from signals for digit 4
+    // and it's not 6 digit
+    // => Then it is digit 0
+    note_entry.signal_patterns.iter().for_each(|signal| {
+        if signal.active_wires.len() == 6
+            && deduced.contains_key(&4)
+            && signal.is_missing_one_char_from(deduced.get(&4).unwrap())
+            && (deduced
+                .values()
+                .filter(|s| s.to_string() == signal.to_string())
+                .count()
+                == 0)
+        // Because digit 6 is  1 char diff and we have already found it, let's ignore it
+        {
+            deduced.insert(0, signal);
+        }
+    });
+
+    // If length is 6 and it's not digit 6 or digit 0
+    // => Then it is digit 9
+    note_entry.signal_patterns.iter().for_each(|signal| {
+        if signal.active_wires.len() == 6
+            && (deduced
+                .values()
+                .filter(|s| s.to_string() == signal.to_string())
+                .count()
+                == 0)
+        {
+            deduced.insert(9, signal);
+        }
+    });
+
+    // Identify digits with length 5
+
+    // If length is 5 and same signals has digit 6 wires
+    // => Then it is digit 5
+    note_entry.signal_patterns.iter().for_each(|signal| {
+        if signal.active_wires.len() == 5
+            && signal.contains_all_wires_of(deduced.get(&6).unwrap())
+        {
+            deduced.insert(5, signal);
+        }
+    });
+
+    // If length is 5 and same signals has digit 6 wires
+    note_entry.signal_patterns.iter().for_each(|signal| {
+        if signal.active_wires.len() == 5
+        && signal.contains_all_wires_of(deduced.get(&9).unwrap())
+        // Because digit 5 is also same as 9 and we have already found it, let's ignore it
+        && (deduced
+            .values()
+            .filter(|s| s.to_string() == signal.to_string())
+            .count()
+            == 0)
+        {
+            deduced.insert(3, signal);
+        }
+    });
+
+    // If length is 5 and it's not digit 5 nor digit 3
+    // => Then it is digit 2
+    note_entry.signal_patterns.iter().for_each(|signal| {
+        if signal.active_wires.len() == 5
+            && (deduced
+                .values()
+                .filter(|s| s.to_string() == signal.to_string())
+                .count()
+                == 0)
+        {
+            deduced.insert(2, signal);
+        }
+    });
+
+    // Reverse the deduced hash : keys are now a sorted string of Signal Patterns.
+    let mut reverse: HashMap<String, String> = HashMap::new();
+    deduced.into_iter().for_each(|(k, v)| {
+        let mut chars: Vec<char> = v.active_wires.to_owned();
+        chars.sort();
+        let _res = reverse.insert(chars.iter().collect::<String>(), k.to_string());
+    });
+
+    // Seek patterns and decode the 4 digit in output
+    note_entry.output_value.iter().for_each(|digit| {
+        let mut sorted_digit: Vec<char> = digit.active_wires.to_owned();
+        sorted_digit.sort();
+
+        let pattern = sorted_digit.iter().collect::<String>();
+        let matching_digit: String = reverse.get(&pattern).unwrap().to_string();
+
+        decoded_digits.push(matching_digit);
+    });
+
+    // Append 4 digits into an integer add sum it
+    let output_value: String = decoded_digits.into_iter().collect();
+    output_value.parse::<usize>().unwrap()
+}
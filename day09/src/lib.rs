@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+
+use common::{Answer, Result, Solver};
+use grid::{Grid, Point};
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Map;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        Map::new(content)
+    }
+
+    /// Sum of the risk levels of every low point
+    fn part1(input: &Self::Input) -> Answer {
+        let mut m = input.clone();
+        m.compute_lowest_points();
+        m.lowest.unwrap().values().sum::<usize>().into()
+    }
+
+    /// Product of the sizes of the three largest bassins
+    fn part2(input: &Self::Input) -> Answer {
+        let mut m = input.clone();
+        m.compute_lowest_points();
+        m.compute_bassins();
+        let mut b_sizes: Vec<usize> = m.bassins.unwrap().into_iter().map(|(_k, v)| v).collect();
+        if b_sizes.len() < 3 {
+            return Answer::Unsolved;
+        }
+        b_sizes.sort();
+        b_sizes.reverse();
+        b_sizes.into_iter().take(3).product::<usize>().into()
+    }
+}
+
+type Height = u8;
+type RiskLevel = usize;
+type Bassin = HashSet<Point>;
+
+type LowestMap = HashMap<Point, RiskLevel>;
+type BassinVec = Vec<(Bassin, usize)>;
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    heights: Grid<Height>,
+    lowest: Option<LowestMap>,
+    bassins: Option<BassinVec>,
+}
+
+impl Map {
+    pub fn new(content: &str) -> Result<Self> {
+        Ok(Map {
+            heights: Grid::parse_digits(content)?,
+            lowest: None,
+            bassins: None,
+        })
+    }
+
+    pub fn compute_lowest_points(&mut self) {
+        let mut lm = LowestMap::new();
+
+        self.heights.iter().for_each(|(loc, height)| {
+            if self.is_lowest_of_adjacents(loc) {
+                lm.insert(loc, *height as RiskLevel + 1);
+            }
+        });
+
+        self.lowest = Some(lm);
+    }
+
+    fn is_lowest_of_adjacents(&self, loc: Point) -> bool {
+        let height = self.heights[loc];
+        self.heights
+            .neighbours8(loc)
+            .all(|adj| self.heights[adj] >= height)
+    }
+
+    /// Neighbours that belong to the same bassin : lower than 9 & not cut off by 9s on a diagonal
+    fn floodable_adjacents_of(&self, loc: Point) -> Vec<Point> {
+        self.heights
+            .neighbours8(loc)
+            .filter(|adj| {
+                let is_diagonal = adj.x != loc.x && adj.y != loc.y;
+                self.heights[*adj] < 9
+                    && (!is_diagonal
+                        || self.heights[Point::new(loc.x, adj.y)] < 9
+                        || self.heights[Point::new(adj.x, loc.y)] < 9)
+            })
+            .collect()
+    }
+
+    pub fn compute_bassins(&mut self) {
+        let mut bl: BassinVec = vec![];
+
+        self.lowest
+            .clone()
+            .unwrap()
+            .into_iter()
+            .for_each(|(loc, _risk)| {
+                let mut candidates_bassin_members: Vec<Point> = self.floodable_adjacents_of(loc);
+                let mut bassin_members = HashSet::new();
+                let mut checked_candidates: HashSet<Point> = HashSet::new();
+                checked_candidates.insert(loc);
+
+                while let Some(c) = candidates_bassin_members.pop() {
+                    checked_candidates.insert(c);
+                    for adj in self.floodable_adjacents_of(c) {
+                        if !bassin_members.contains(&adj) {
+                            bassin_members.insert(adj);
+                        }
+                        if !checked_candidates.contains(&adj) {
+                            candidates_bassin_members.push(adj);
+                        }
+                        checked_candidates.insert(adj);
+                    }
+                }
+                let members_count = bassin_members.len();
+                // println!(
+                //     "new Bassin found {:?} members => {:?}",
+                //     members_count, bassin_members
+                // );
+                bl.push((bassin_members, members_count));
+            });
+
+        self.bassins = Some(bl);
+    }
+}
@@ -0,0 +1,153 @@
+use common::{parse, Answer, Result, Solver};
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u8 = 10;
+
+    /// The navigation subsystem, one chunk line per line
+    type Input = Vec<String>;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        content
+            .lines()
+            .map(
+                |line| match line.char_indices().find(|(_, c)| !"()[]{}<>".contains(*c)) {
+                    Some((i, c)) => Err(parse::error(
+                        content,
+                        &line[i..i + c.len_utf8()],
+                        "one of `()[]{}<>`",
+                    )),
+                    None => Ok(line.to_string()),
+                },
+            )
+            .collect()
+    }
+
+    /// Find corrupted lines & compute score
+    fn part1(input: &Self::Input) -> Answer {
+        input
+            .iter()
+            .map(|line| parse_line(line))
+            .filter(|(status, _)| *status == Status::Corrupted)
+            .map(|(_, score)| score)
+            .sum::<Score>()
+            .into()
+    }
+
+    /// Complete incomplete lines & retrieve the middle score
+    fn part2(input: &Self::Input) -> Answer {
+        let mut incomplete_scores: Vec<Score> = input
+            .iter()
+            .map(|line| parse_line(line))
+            .filter(|(status, _)| *status == Status::Incomplete)
+            .map(|(_, score)| score)
+            .collect();
+
+        incomplete_scores.sort();
+        let index = incomplete_scores.len() / 2; // No need to add 1 because vec index starts at 0
+        incomplete_scores
+            .get(index)
+            .map_or(Answer::Unsolved, |score| (*score).into())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Complete,
+    Incomplete,
+    Corrupted,
+}
+
+pub type Score = u64;
+
+fn is_closing_for(open_char: char, closing_char: char) -> bool {
+    match (open_char, closing_char) {
+        ('(', ')') | ('[', ']') | ('{', '}') | ('<', '>') => true,
+        (_, _) => false,
+    }
+}
+
+fn matching_closing_char_for(open_char: char) -> char {
+    match open_char {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        _ => panic!("No Closing caracter matching {}", open_char),
+    }
+}
+
+fn illegal_char_score(c: char) -> u64 {
+    match c {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        _ => 0,
+    }
+}
+
+fn completed_char_score(c: char) -> u64 {
+    match c {
+        ')' => 1,
+        ']' => 2,
+        '}' => 3,
+        '>' => 4,
+        _ => 0,
+    }
+}
+
+pub fn parse_line(line: &str) -> (Status, Score) {
+    let mut stack = vec![];
+    let mut status: Status = Status::Incomplete;
+    let mut score = 0;
+
+    for c in line.chars() {
+        match c {
+            '(' | '[' | '{' | '<' => {
+                stack.push(c);
+            }
+            ')' | ']' | '}' | '>' => {
+                if let Some(oc) = stack.pop() {
+                    if !is_closing_for(oc, c) {
+                        status = Status::Corrupted;
+                        score += illegal_char_score(c);
+                        break;
+                    }
+                }
+            }
+            _ => (),
+        };
+    }
+    if status != Status::Corrupted {
+        if stack.is_empty() {
+            status = Status::Complete;
+        } else {
+            status = Status::Incomplete;
+            score = 0;
+            while let Some(x) = stack.pop() {
+                let matching_char = matching_closing_char_for(x);
+                score = (score * 5) + completed_char_score(matching_char);
+            }
+        }
+    }
+    (status, score)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Answer, Solver};
+
+    use crate::Day10;
+
+    #[test]
+    fn it_needs_an_incomplete_line_for_the_middle_score() {
+        let lines = Day10::parse("()\n(]").unwrap();
+        assert_eq!(Day10::part1(&lines), Answer::Number(57));
+        assert_eq!(Day10::part2(&lines), Answer::Unsolved);
+
+        let lines = Day10::parse("()\n<\n([\n{").unwrap();
+        assert_eq!(Day10::part2(&lines), Answer::Number(4));
+    }
+}
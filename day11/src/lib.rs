@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use common::{Answer, Result, Solver};
+use grid::Point;
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Grid;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        Grid::new(content)
+    }
+
+    /// Count flashes during the first 100 steps
+    fn part1(input: &Self::Input) -> Answer {
+        let mut g = input.clone();
+        let mut flash_counter = 0;
+        for _ in 1..=100 {
+            flash_counter += g.do_step().len();
+        }
+        flash_counter.into()
+    }
+
+    /// First step during which every octopus flashes
+    fn part2(input: &Self::Input) -> Answer {
+        let mut g = input.clone();
+        let mut step = 1;
+        while g.do_step().len() != g.octopus_count() {
+            step += 1;
+        }
+        step.into()
+    }
+}
+
+type EnergyLevel = u8;
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    levels: grid::Grid<EnergyLevel>,
+}
+
+impl Grid {
+    pub fn new(content: &str) -> Result<Self> {
+        Ok(Grid {
+            levels: grid::Grid::parse_digits(content)?,
+        })
+    }
+
+    pub fn octopus_count(&self) -> usize {
+        self.levels.width() * self.levels.height()
+    }
+
+    pub fn do_step(&mut self) -> HashSet<Point> {
+        let mut flashing_in_step: HashSet<Point> = HashSet::new();
+        let mut increasing_in_step: Vec<Point> = vec![];
+
+        // Increase all octopus energy level by 1
+        self.levels.values_mut().for_each(|lvl| {
+            *lvl += 1;
+        });
+
+        // Any octopus with level > 9 Flash & mark neighbors
+        self.levels.iter().for_each(|(loc, lvl)| {
+            if *lvl > 9 {
+                // Flash
+                flashing_in_step.insert(loc);
+                increasing_in_step.extend(self.levels.neighbours8(loc));
+            }
+        });
+
+        while let Some(increased_loc) = increasing_in_step.pop() {
+            // Increase level by 1
+            let lvl = &mut self.levels[increased_loc];
+            *lvl += 1;
+            // Try flash and if already flashed we won't process its neighbors
+            if *lvl > 9 && flashing_in_step.insert(increased_loc) {
+                //Treat Neighbors
+                increasing_in_step.extend(self.levels.neighbours8(increased_loc));
+            }
+        }
+
+        // Finish
+        flashing_in_step.iter().for_each(|loc| {
+            self.levels[*loc] = 0;
+        });
+
+        flashing_in_step
+    }
+}
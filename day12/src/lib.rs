@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use common::{parse, Answer, Result, Solver};
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = CaveMap;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        build_map(content)
+    }
+
+    /// Traverse recursively, small caves are visited at most once
+    fn part1(input: &Self::Input) -> Answer {
+        input
+            .clone()
+            .traverse(vec![vec!["start".to_string()]], false)
+            .len()
+            .into()
+    }
+
+    /// Traverse recursively, a single small cave can be visited twice
+    fn part2(input: &Self::Input) -> Answer {
+        input
+            .clone()
+            .traverse(vec![vec!["start".to_string()]], true)
+            .len()
+            .into()
+    }
+}
+
+pub type CaveMap = HashMap<String, Vec<String>>;
+
+pub trait Segmentable {
+    fn add_segment(&mut self, start: &str, end: &str);
+    fn traverse(
+        &mut self,
+        paths: Vec<Vec<String>>,
+        allow_small_twice: bool,
+    ) -> Vec<Vec<String>>;
+}
+
+impl Segmentable for CaveMap {
+    fn add_segment(&mut self, start: &str, end: &str) {
+        self.entry(start.to_string())
+            .or_default()
+            .push(end.to_string());
+        self.entry(end.to_string())
+            .or_default()
+            .push(start.to_string());
+    }
+
+    fn traverse(
+        &mut self,
+        paths: Vec<Vec<String>>,
+        allow_small_twice: bool,
+    ) -> Vec<Vec<String>> {
+        let mut cloned_paths = paths.clone();
+        let mut added = false;
+        let mut new_paths: Vec<Vec<String>> = vec![];
+
+        while let Some(path) = cloned_paths.pop() {
+            let last_element = path.last().unwrap();
+            if last_element == "end" {
+                new_paths.push(path);
+                continue;
+            }
+            if let Some(neighborlist) = self.get_mut(last_element) {
+                let base_path = path.clone();
+                neighborlist.iter().for_each(|neighbor| {
+                    let visited = path.contains(neighbor);
+                    let big_cave = *neighbor == neighbor.to_uppercase();
+                    let mut can_be_visited_twice = allow_small_twice;
+
+                    if allow_small_twice {
+                        let mut dedup_path: Vec<String> = path
+                            .clone()
+                            .into_iter()
+                            .filter(|x| *x != x.to_uppercase())
+                            .collect();
+                        let ref_path: Vec<String> = path
+                            .clone()
+                            .into_iter()
+                            .filter(|x| *x != x.to_uppercase())
+                            .collect();
+                        dedup_path.sort();
+                        dedup_path.dedup();
+                        can_be_visited_twice = (neighbor != "start")
+                            && (neighbor != "end")
+                            && (dedup_path.len() == ref_path.len());
+                    }
+
+                    // println!("can be visited twice {:?}", can_be_visited_twice);
+                    if big_cave || !visited || can_be_visited_twice {
+                        let mut cloned = base_path.clone();
+                        cloned.push(neighbor.clone());
+                        // println!("new ongoing path {:?}", cloned);
+                        new_paths.push(cloned);
+                        added = true;
+                    }
+                });
+            }
+        }
+
+        // println!("new_paths {:?}", new_paths);
+        if added {
+            // At least one path has increased, we need to try to increase it.
+            self.traverse(new_paths, allow_small_twice)
+        } else {
+            // No paths added, we cannot continue => we only ouput those that end at "end"
+            paths
+                .into_iter()
+                .filter(|r| r.last().unwrap() == "end")
+                .collect()
+        }
+    }
+}
+
+/// Build the map by adding segments
+pub fn build_map(content: &str) -> Result<CaveMap> {
+    let mut map: CaveMap = CaveMap::new();
+    for segment in content.lines() {
+        let mut iter = segment.split('-');
+        let from = parse::field(content, segment, iter.next(), "a cave")?;
+        let to = parse::field(content, segment, iter.next(), "`-` & a second cave")?;
+        for cave in [from, to] {
+            if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(parse::error(content, cave, "a cave name"));
+            }
+        }
+        map.add_segment(from, to);
+    }
+    Ok(map)
+}
@@ -0,0 +1,224 @@
+use core::fmt;
+use std::cmp::Ordering;
+
+use common::{parse, Answer, Result, Solver};
+use grid::{Grid, Point};
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Paper;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        Paper::new(content)
+    }
+
+    /// Visible dots after the first fold only
+    fn part1(input: &Self::Input) -> Answer {
+        if input.foldings.is_empty() {
+            return Answer::Unsolved;
+        }
+        let mut paper = input.clone();
+        match paper.fold(Some(1)) {
+            Some(()) => paper.visible_dot_count().into(),
+            None => Answer::Failed(OFF_THE_PAPER.to_string()),
+        }
+    }
+
+    /// The code drawn once every fold has been done
+    fn part2(input: &Self::Input) -> Answer {
+        let mut paper = input.clone();
+        match paper.fold(None) {
+            Some(()) => paper.to_string().into(),
+            None => Answer::Failed(OFF_THE_PAPER.to_string()),
+        }
+    }
+}
+
+const OFF_THE_PAPER: &str = "a fold puts dots beyond the edge of the paper";
+
+#[derive(Debug, PartialEq, Clone)]
+enum Direction {
+    X,
+    Y,
+}
+
+type Position = usize;
+type Fold = (Direction, Position);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Paper {
+    /// `true` where there is a dot
+    dots: Grid<bool>,
+    foldings: Vec<Fold>,
+}
+
+impl Paper {
+    pub fn new(content: &str) -> Result<Self> {
+        let mut coords = vec![];
+        let mut foldings = vec![];
+
+        let mut split = content.split("\n\n");
+        let coords_str = split.next().unwrap_or(content);
+        let foldings_str = parse::field(
+            content,
+            content,
+            split.next(),
+            "an empty line & the fold instructions",
+        )?;
+
+        for coord in coords_str.lines() {
+            let mut iter = coord.split(',');
+            let x = parse::field(content, coord, iter.next(), "a `x` coordinate")?;
+            let y = parse::field(content, coord, iter.next(), "`,` & a `y` coordinate")?;
+            coords.push(Point::new(
+                parse::number(content, x)?,
+                parse::number(content, y)?,
+            ));
+        }
+
+        for fold in foldings_str.lines() {
+            let Some(instruction) = fold.strip_prefix("fold along ") else {
+                return Err(parse::error(
+                    content,
+                    fold,
+                    "`fold along x=N` or `fold along y=N`",
+                ));
+            };
+            let (direction, position) = match instruction.split_once('=') {
+                Some(("x", position)) => (Direction::X, position),
+                Some(("y", position)) => (Direction::Y, position),
+                _ => return Err(parse::error(content, instruction, "`x=N` or `y=N`")),
+            };
+            foldings.push((direction, parse::number(content, position)?));
+        }
+
+        let width = coords.iter().map(|c| c.x + 1).max().unwrap_or(0);
+        let height = coords.iter().map(|c| c.y + 1).max().unwrap_or(0);
+        let mut dots = Grid::new(width, height, false);
+        for coord in coords {
+            dots[coord] = true;
+        }
+
+        Ok(Paper { dots, foldings })
+    }
+
+    /// `None` when a dot would be folded beyond the edge of the paper, which is then left as it was
+    fn fold_once_at(&mut self, direction: Direction, position: Position) -> Option<()> {
+        let mut folded = match direction {
+            Direction::X => Grid::new(position, self.dots.height(), false),
+            Direction::Y => Grid::new(self.dots.width(), position, false),
+        };
+        for (coord, _) in self.dots.iter().filter(|(_, dot)| **dot) {
+            let folded_coord = match direction {
+                Direction::X => match coord.x.cmp(&position) {
+                    Ordering::Greater => {
+                        Point::new(position.checked_sub(coord.x - position)?, coord.y)
+                    }
+                    Ordering::Less => coord,
+                    Ordering::Equal => continue,
+                },
+                Direction::Y => match coord.y.cmp(&position) {
+                    Ordering::Greater => {
+                        Point::new(coord.x, position.checked_sub(coord.y - position)?)
+                    }
+                    Ordering::Less => coord,
+                    Ordering::Equal => continue,
+                },
+            };
+            folded[folded_coord] = true;
+        }
+        self.dots = folded;
+        Some(())
+    }
+
+    /// Runs the first `limit` folds, or all of them. `None` when one of them puts dots beyond the edge of
+    /// the paper, the paper being left as it was before that fold.
+    pub fn fold(&mut self, limit: Option<u16>) -> Option<()> {
+        let count = limit.map_or(self.foldings.len(), usize::from);
+        for (direction, position) in self.foldings.clone().into_iter().take(count) {
+            self.fold_once_at(direction, position)?;
+        }
+        Some(())
+    }
+
+    pub fn visible_dot_count(self) -> usize {
+        self.dots.iter().filter(|(_, dot)| **dot).count()
+    }
+}
+
+/// Size of the rendered paper : the 8 letters of the code, whatever the size left by the last fold
+const SCREEN_WIDTH: usize = 41;
+const SCREEN_HEIGHT: usize = 7;
+
+impl fmt::Display for Paper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut grid = String::new();
+        for y in 0..SCREEN_HEIGHT {
+            for x in 0..SCREEN_WIDTH {
+                match self.dots.get(Point::new(x, y)) {
+                    Some(true) => grid.push('#'),
+                    _ => grid.push('.'),
+                }
+            }
+            grid.push('\n');
+        }
+        write!(f, "{}", grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Answer, Solver};
+    use grid::{Grid, Point};
+
+    use crate::{Day13, Direction, Paper};
+
+    const SIMPLE_CONTENT: &str = r#"6,10
+0,14
+
+fold along y=7"#;
+
+    #[test]
+    fn it_can_properly_initialize_a_paper() {
+        let expected_paper: Paper = Paper::new(SIMPLE_CONTENT).unwrap();
+
+        let mut dots = Grid::new(7, 15, false);
+        dots[Point::new(6, 10)] = true;
+        dots[Point::new(0, 14)] = true;
+
+        let foldings = vec![(Direction::Y, 7)];
+
+        assert_eq!(expected_paper, Paper { dots, foldings });
+    }
+
+    #[test]
+    fn it_can_fold() {
+        let mut expected_paper: Paper = Paper::new(SIMPLE_CONTENT).unwrap();
+        expected_paper.fold(Some(1)).unwrap();
+
+        let mut dots = Grid::new(7, 7, false);
+        dots[Point::new(0, 0)] = true;
+        dots[Point::new(6, 4)] = true;
+
+        let foldings = vec![(Direction::Y, 7)];
+
+        assert_eq!(expected_paper, Paper { dots, foldings });
+    }
+
+    #[test]
+    fn it_cannot_fold_dots_beyond_the_edge() {
+        let paper = Paper::new("1,1\n1,3\n\nfold along x=5\nfold along y=1").unwrap();
+        assert_eq!(Day13::part1(&paper), Answer::Number(2));
+        assert!(matches!(Day13::part2(&paper), Answer::Failed(_)));
+
+        let mut folded = paper.clone();
+        assert_eq!(folded.fold(None), None);
+        assert_eq!(folded.visible_dot_count(), 2);
+
+        let unfolded = Paper::new("1,1\n\n").unwrap();
+        assert_eq!(Day13::part1(&unfolded), Answer::Unsolved);
+    }
+}
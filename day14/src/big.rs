@@ -0,0 +1,117 @@
+//! Just enough of an arbitrary precision unsigned integer to count elements exactly, whatever the step.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+/// Limbs are stored little endian, without trailing zeros : zero has no limb at all
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    fn trimmed(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    /// Divides in place by a small `divisor`, returning the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let current = (remainder << 32) | *limb as u64;
+            *limb = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+        *self = BigUint::trimmed(std::mem::take(&mut self.limbs));
+        remainder as u32
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        BigUint::trimmed(vec![n as u32, (n >> 32) as u32])
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (self.limbs, other.limbs)
+        } else {
+            (other.limbs, self.limbs)
+        };
+
+        let mut sum = Vec::with_capacity(long.len() + 1);
+        let mut carry = 0u64;
+        for (i, limb) in long.iter().enumerate() {
+            let current = *limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+            sum.push(current as u32);
+            carry = current >> 32;
+        }
+        sum.push(carry as u32);
+        BigUint::trimmed(sum)
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        if self.limbs.is_empty() || other.limbs.is_empty() {
+            return BigUint::default();
+        }
+
+        let mut product = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let current = product[i + j] as u64 + *a as u64 * *b as u64 + carry;
+                product[i + j] = current as u32;
+                carry = current >> 32;
+            }
+            product[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint::trimmed(product)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Peel off 9 decimal digits at a time, least significant first
+        let mut n = self.clone();
+        let mut chunks = vec![];
+        loop {
+            chunks.push(n.div_rem_small(1_000_000_000));
+            if n.limbs.is_empty() {
+                break;
+            }
+        }
+
+        let mut digits = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+        f.pad(&digits)
+    }
+}
@@ -0,0 +1,224 @@
+//! Positional & range queries on the polymer after any number of steps, without building it.
+//!
+//! After `n` steps, a pair `AB` of the template has expanded into the pairs it produces, each expanded for
+//! `n - 1` steps : the polymer is the leaves of that tree. Knowing how many elements every subtree holds,
+//! a query walks down a single branch of the tree, skipping whole subtrees on its way, so it costs
+//! `n` levels for a polymer of about `2^n` elements.
+//!
+//! Lengths & counts are capped to `u128::MAX` : deeper polymers can still be queried, as long as the
+//! positions fit in a `u128`. Once every length is capped, or no expansion grows anymore, the levels
+//! below are all the same : only the levels down to there are built, & a query walking through the
+//! levels above it skips the loops it goes round.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::{produced_pairs, Manual};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expansion {
+    steps: usize,
+    pairs: Vec<(char, char)>,
+    elements: Vec<char>,
+    /// Pairs of the template, as indexes in `pairs`
+    template: Vec<usize>,
+    /// The last element of the template, the only one that isn't the first element of a pair
+    last: char,
+    /// `children[p]` are the pairs `p` turns into after one step
+    children: Vec<Vec<usize>>,
+    /// `lengths[d][p]` is how many elements pair `p` expands into after `d` steps, its second one excluded.
+    /// The last level also holds for any deeper step.
+    lengths: Vec<Vec<u128>>,
+    /// `counts[d][p][e]` is how many elements `e` are in the expansion of `p` after `d` steps
+    counts: Vec<Vec<Vec<u128>>>,
+}
+
+impl Manual {
+    /// The polymer after `steps` steps, ready to be queried. Only possible when every rule is a pair
+    /// insertion rule.
+    pub fn expansion(&self, steps: usize) -> Option<Expansion> {
+        let rules = self.pair_rules()?;
+        let pairs = self.reachable_pairs(&rules);
+        let index: HashMap<(char, char), usize> = pairs
+            .iter()
+            .enumerate()
+            .map(|(i, pair)| (*pair, i))
+            .collect();
+
+        let template_elements: Vec<char> = self.template.chars().collect();
+        let mut elements: Vec<char> = pairs.iter().flat_map(|(a, b)| [*a, *b]).collect();
+        elements.extend(&template_elements);
+        elements.sort();
+        elements.dedup();
+
+        let children: Vec<Vec<usize>> = pairs
+            .iter()
+            .map(|pair| {
+                produced_pairs(&rules, *pair)
+                    .iter()
+                    .map(|child| index[child])
+                    .collect()
+            })
+            .collect();
+
+        // Without a step, a pair holds its first element only
+        let mut lengths = vec![vec![1; pairs.len()]];
+        let mut counts = vec![pairs
+            .iter()
+            .map(|(first, _)| {
+                let mut count = vec![0; elements.len()];
+                count[elements.binary_search(first).unwrap()] = 1;
+                count
+            })
+            .collect::<Vec<_>>()];
+        for depth in 1..=steps {
+            let (below_lengths, below_counts) = (&lengths[depth - 1], &counts[depth - 1]);
+            let mut level_lengths = vec![0u128; pairs.len()];
+            let mut level_counts = vec![vec![0u128; elements.len()]; pairs.len()];
+            for (p, pair_children) in children.iter().enumerate() {
+                for child in pair_children {
+                    level_lengths[p] = level_lengths[p].saturating_add(below_lengths[*child]);
+                    for (total, count) in level_counts[p].iter_mut().zip(&below_counts[*child]) {
+                        *total = total.saturating_add(*count);
+                    }
+                }
+            }
+            // Every deeper level is the same as this one : the lengths are all capped, or stopped growing
+            let last = level_lengths == lengths[depth - 1];
+            lengths.push(level_lengths);
+            counts.push(level_counts);
+            if last {
+                break;
+            }
+        }
+
+        Some(Expansion {
+            steps,
+            template: template_elements
+                .windows(2)
+                .map(|pair| index[&(pair[0], pair[1])])
+                .collect(),
+            last: template_elements[template_elements.len() - 1],
+            pairs,
+            elements,
+            children,
+            lengths,
+            counts,
+        })
+    }
+}
+
+impl Expansion {
+    /// How many elements the polymer holds, capped to `u128::MAX`
+    pub fn len(&self) -> u128 {
+        let level = self.level(self.steps);
+        self.template
+            .iter()
+            .fold(1u128, |len, p| len.saturating_add(self.lengths[level][*p]))
+    }
+
+    /// The polymer always holds at least the last element of its template
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The element at `index`, `None` past the end of the polymer
+    pub fn element_at(&self, index: u128) -> Option<char> {
+        let mut index = index;
+        for p in &self.template {
+            let length = self.lengths[self.level(self.steps)][*p];
+            if index < length {
+                return Some(self.descend(*p, index, |_, _| ()));
+            }
+            index -= length;
+        }
+        (index == 0).then_some(self.last)
+    }
+
+    /// How many times each element appears in `range` of the polymer, `None` when the range goes past its end
+    pub fn counts_in(&self, range: Range<u128>) -> Option<HashMap<char, u128>> {
+        if range.start > range.end || range.end > self.len() {
+            return None;
+        }
+
+        let end = self.prefix_counts(range.end);
+        let start = self.prefix_counts(range.start);
+        Some(
+            self.elements
+                .iter()
+                .zip(end.iter().zip(start))
+                .map(|(element, (end, start))| (*element, end - start))
+                .collect(),
+        )
+    }
+
+    /// Counts of the elements before `index`
+    fn prefix_counts(&self, index: u128) -> Vec<u128> {
+        let mut totals = vec![0u128; self.elements.len()];
+        let mut add = |level: usize, p: usize| {
+            for (total, count) in totals.iter_mut().zip(&self.counts[level][p]) {
+                *total += count;
+            }
+        };
+
+        let level = self.level(self.steps);
+        let mut index = index;
+        for p in &self.template {
+            let length = self.lengths[level][*p];
+            if index < length {
+                self.descend(*p, index, &mut add);
+                return totals;
+            }
+            // Whole subtrees before the index are at most `index` elements long, their counts are exact
+            add(level, *p);
+            index -= length;
+        }
+        if index > 0 {
+            totals[self.elements.binary_search(&self.last).unwrap()] += 1;
+        }
+        totals
+    }
+
+    /// The built level that holds for `depth` steps
+    fn level(&self, depth: usize) -> usize {
+        depth.min(self.lengths.len() - 1)
+    }
+
+    /// Walks down the expansion of pair `p` to the element at `index` in it & returns it.
+    /// `skipped` is called with the level & pair of every subtree passed on the way.
+    fn descend(&self, p: usize, index: u128, mut skipped: impl FnMut(usize, usize)) -> char {
+        let last = self.lengths.len() - 1;
+        let (mut p, mut index, mut depth) = (p, index, self.steps);
+        // Above the last level, the walk only depends on the pair & the index : coming back to both is a loop
+        let mut visits: Vec<Option<(usize, u128)>> = vec![None; self.pairs.len()];
+        while depth > 0 {
+            if depth > last {
+                if self.lengths[last][p] < u128::MAX {
+                    // An expansion that stopped growing is the same at the last level
+                    depth = last;
+                    continue;
+                }
+                if let Some((seen, seen_index)) = visits[p] {
+                    if seen_index == index {
+                        let period = seen - depth;
+                        depth -= (depth - last) / period * period;
+                    }
+                }
+                visits[p] = Some((depth, index));
+            }
+
+            depth -= 1;
+            let level = depth.min(last);
+            for child in &self.children[p] {
+                let length = self.lengths[level][*child];
+                if index < length {
+                    p = *child;
+                    break;
+                }
+                skipped(level, *child);
+                index -= length;
+            }
+        }
+        self.pairs[p].0
+    }
+}
@@ -0,0 +1,443 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use common::{parse, Answer, Result, Solver};
+
+mod big;
+mod expansion;
+mod matrix;
+mod rules;
+
+pub use big::BigUint;
+pub use expansion::Expansion;
+pub use matrix::{Count, Modulo, PairMatrix};
+pub use rules::{Rule, Rules};
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Manual;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        Manual::new(content)
+    }
+
+    /// Most common minus least common element quantity after 10 steps
+    fn part1(input: &Self::Input) -> Answer {
+        let mut manual = input.clone();
+        for _ in 1..=10 {
+            manual.step();
+        }
+
+        let freq = manual.frequencies();
+        (freq.values().max().unwrap() - freq.values().min().unwrap()).into()
+    }
+
+    /// Same after 40 steps, too many to build the polymer : only its pairs are counted
+    fn part2(input: &Self::Input) -> Answer {
+        let Some(mut polymer) = input.pair_polymer() else {
+            return Answer::Unsolved;
+        };
+        for _ in 1..=40 {
+            if let Err(overflow) = polymer.step() {
+                return Answer::Failed(overflow.to_string());
+            }
+        }
+
+        let freq = polymer.frequencies();
+        (freq.values().max().unwrap() - freq.values().min().unwrap()).into()
+    }
+}
+
+type PolymerTemplate = String;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Manual {
+    template: PolymerTemplate,
+    rules: Rules,
+}
+
+impl Manual {
+    pub fn new(content: &str) -> Result<Self> {
+        let mut rules = vec![];
+
+        let mut split = content.split("\n\n");
+        let template = split.next().unwrap_or(content);
+        if template.is_empty() || template.contains('\n') {
+            return Err(parse::error(content, template, "a polymer template"));
+        }
+        let rules_str = parse::field(
+            content,
+            content,
+            split.next(),
+            "an empty line & the insertion rules",
+        )?;
+
+        for rule in rules_str.lines() {
+            rules.push(Rule::parse(content, rule)?);
+        }
+
+        Ok(Manual {
+            template: template.to_string(),
+            rules: Rules::new(rules),
+        })
+    }
+
+    pub fn step(&mut self) {
+        let polymer: Vec<char> = self.template.chars().collect();
+        self.template = self.rules.rewrite(&polymer).into_iter().collect();
+    }
+
+    /// The template as counts of its adjacent pairs, to run steps on polymers too long to be built.
+    /// Only possible when every rule is a pair insertion rule.
+    pub fn pair_polymer(&self) -> Option<PairPolymer> {
+        let rules = self.pair_rules()?;
+
+        let elements: Vec<char> = self.template.chars().collect();
+        let mut pairs = PairCounts::new();
+        for pair in elements.windows(2) {
+            *pairs.entry((pair[0], pair[1])).or_insert(0) += 1;
+        }
+
+        Some(PairPolymer {
+            pairs,
+            steps: 0,
+            last: elements[elements.len() - 1],
+            rules,
+        })
+    }
+
+    /// What is inserted in each pair, `None` unless every rule is a pair insertion rule
+    fn pair_rules(&self) -> Option<PairRules> {
+        let mut rules = PairRules::new();
+        for rule in self.rules.iter() {
+            // By priority : the first rule of a pair is the one that applies
+            rules
+                .entry(rule.pair()?)
+                .or_insert_with(|| rule.insertion().to_vec());
+        }
+        Some(rules)
+    }
+
+    /// Every pair that can appear in the polymer, from the template on, sorted
+    fn reachable_pairs(&self, rules: &PairRules) -> Vec<(char, char)> {
+        let elements: Vec<char> = self.template.chars().collect();
+        let mut pairs = vec![];
+        let mut seen = HashSet::new();
+        let mut to_visit: Vec<(char, char)> = elements.windows(2).map(|p| (p[0], p[1])).collect();
+        while let Some(pair) = to_visit.pop() {
+            if seen.insert(pair) {
+                pairs.push(pair);
+                to_visit.extend(produced_pairs(rules, pair));
+            }
+        }
+        pairs.sort();
+        pairs
+    }
+
+    pub fn frequencies(&self) -> HashMap<char, u32> {
+        let mut freq = HashMap::new();
+        for ch in self.template.chars() {
+            let counter = freq.entry(ch).or_insert(0);
+            *counter += 1;
+        }
+        freq
+    }
+}
+
+type PairCounts = HashMap<(char, char), u128>;
+type PairRules = HashMap<(char, char), Vec<char>>;
+
+/// The pairs a pair turns into after one step : `AB -> XY` turns `AB` into `AX`, `XY` & `YB`
+fn produced_pairs(rules: &PairRules, (first, second): (char, char)) -> Vec<(char, char)> {
+    let inserted = rules.get(&(first, second)).map_or(&[][..], Vec::as_slice);
+    let elements: Vec<char> = [first]
+        .iter()
+        .chain(inserted)
+        .chain(&[second])
+        .copied()
+        .collect();
+    elements.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+/// A polymer known only by how many times each pair of adjacent elements appears in it.
+/// A step turns every `AB` pair with an `AB -> C` rule into `AC` & `CB`, so its cost doesn't depend on the
+/// polymer length, which doubles at each step.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PairPolymer {
+    pairs: PairCounts,
+    /// How many steps were run, so that an overflow tells which one
+    steps: usize,
+    /// The last element never changes : it is the only one that starts no pair
+    last: char,
+    rules: PairRules,
+}
+
+/// A step making the polymer too long for its length to fit in a `u128`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub step: usize,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the polymer is too long to be counted in a u128 after step {}",
+            self.step
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+impl PairPolymer {
+    /// Runs one more step, or leaves the polymer as it is when its length would overflow : every count
+    /// then stays exact
+    pub fn step(&mut self) -> std::result::Result<(), Overflow> {
+        let overflow = Overflow {
+            step: self.steps + 1,
+        };
+        let mut pairs = PairCounts::with_capacity(self.pairs.len());
+        // The last element is the only one that starts no pair
+        let mut len: u128 = 1;
+        for (&pair, &count) in &self.pairs {
+            for produced in produced_pairs(&self.rules, pair) {
+                let counter = pairs.entry(produced).or_insert(0);
+                *counter = counter.checked_add(count).ok_or(overflow)?;
+                len = len.checked_add(count).ok_or(overflow)?;
+            }
+        }
+        self.pairs = pairs;
+        self.steps += 1;
+        Ok(())
+    }
+
+    pub fn frequencies(&self) -> HashMap<char, u128> {
+        // Every element starts a pair except for the last one, so no count exceeds the polymer length
+        let mut freq = HashMap::from([(self.last, 1)]);
+        for (&(first, _), &count) in &self.pairs {
+            *freq.entry(first).or_insert(0) += count;
+        }
+        freq
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{BigUint, Manual, Modulo};
+
+    const CONTENT: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    #[test]
+    fn it_counts_the_same_frequencies_from_pairs() {
+        let mut manual = Manual::new(CONTENT).unwrap();
+        let mut polymer = manual.pair_polymer().unwrap();
+        for _ in 0..=10 {
+            let expected = manual
+                .frequencies()
+                .into_iter()
+                .map(|(element, count)| (element, count as u128))
+                .collect();
+            assert_eq!(polymer.frequencies(), expected);
+            manual.step();
+            polymer.step().unwrap();
+        }
+    }
+
+    #[test]
+    fn it_stops_counting_pairs_before_overflowing() {
+        let manual = Manual::new(CONTENT).unwrap();
+        let matrix = manual.pair_matrix().unwrap();
+        let mut polymer = manual.pair_polymer().unwrap();
+
+        // The polymer doubles at each step, so its length overflows a u128 after some 125 steps
+        let mut steps = 0;
+        let overflow = loop {
+            match polymer.step() {
+                Ok(()) => steps += 1,
+                Err(overflow) => break overflow,
+            }
+            assert!(steps < 200, "no overflow after {} steps", steps);
+        };
+        assert_eq!(overflow.step, steps + 1);
+        assert!((120..130).contains(&steps));
+
+        // The counts of the last step that fits are still exact, & stay so after the failed steps
+        assert!(polymer.step().is_err());
+        let exact = matrix.frequencies::<BigUint>(steps as u64);
+        let freq = polymer.frequencies();
+        assert_eq!(freq.len(), exact.len());
+        for (element, count) in freq {
+            assert_eq!(exact[&element].to_string(), count.to_string());
+        }
+    }
+
+    #[test]
+    fn it_can_rewrite_with_general_rules() {
+        // Contexts of any length, multi-element insertions & an empty context
+        let mut manual = Manual::new("ABAB\n\nAB|A -> XY\n|B -> -").unwrap();
+        manual.step();
+        assert_eq!(manual.template, "A-BXYA-B");
+        assert_eq!(manual.pair_polymer(), None);
+
+        // The most specific rule wins, then the last declared one
+        let mut manual = Manual::new("ABAB\n\nA|B -> x\nA|B -> y\nBA|B -> z").unwrap();
+        manual.step();
+        assert_eq!(manual.template, "AyBAzB");
+
+        assert!(Manual::new("AB\n\nABC -> X").is_err());
+        assert!(Manual::new("AB\n\n| -> X").is_err());
+        assert!(Manual::new("AB\n\nA|B -> ").is_err());
+    }
+
+    #[test]
+    fn it_counts_pairs_with_multi_element_insertions() {
+        let mut manual = Manual::new("NNCB\n\nNN -> XY\nCB -> H\nNC|B -> Z").unwrap();
+        assert_eq!(manual.pair_polymer(), None);
+
+        manual = Manual::new("NNCB\n\nNN -> XY\nCB -> HN\nXY -> N").unwrap();
+        let mut polymer = manual.pair_polymer().unwrap();
+        for _ in 0..=5 {
+            let expected = manual
+                .frequencies()
+                .into_iter()
+                .map(|(element, count)| (element, count as u128))
+                .collect();
+            assert_eq!(polymer.frequencies(), expected);
+            manual.step();
+            polymer.step().unwrap();
+        }
+    }
+
+    #[test]
+    fn it_counts_the_same_frequencies_from_the_matrix() {
+        let manual = Manual::new(CONTENT).unwrap();
+        let matrix = manual.pair_matrix().unwrap();
+
+        let mut polymer = manual.pair_polymer().unwrap();
+        for _ in 1..=40 {
+            polymer.step().unwrap();
+        }
+        assert_eq!(matrix.frequencies::<u128>(40), polymer.frequencies());
+
+        // Every kind of count agrees while the exact counts still fit in a u128
+        let exact = matrix.frequencies::<u128>(100);
+        let big = matrix.frequencies::<BigUint>(100);
+        let modulo = matrix.frequencies::<Modulo<1_000_000_007>>(100);
+        for (element, count) in exact {
+            assert_eq!(big[&element].to_string(), count.to_string());
+            assert_eq!(
+                modulo[&element],
+                Modulo::from((count % 1_000_000_007) as u64)
+            );
+        }
+    }
+
+    #[test]
+    fn it_finds_the_growth_rate_and_limiting_proportions() {
+        let matrix = Manual::new(CONTENT).unwrap().pair_matrix().unwrap();
+        assert!((matrix.growth_rate() - 2.0).abs() < 1e-9);
+
+        let proportions = matrix.limiting_proportions();
+        assert!((proportions.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        // After 40 steps, B is already 2192039569602 of the 3298534883329 elements
+        assert!((proportions[&'B'] - 2.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn it_can_query_the_polymer_without_building_it() {
+        let mut manual = Manual::new(CONTENT).unwrap();
+        let expansion = manual.expansion(10).unwrap();
+        for _ in 1..=10 {
+            manual.step();
+        }
+
+        let polymer: Vec<char> = manual.template.chars().collect();
+        assert_eq!(expansion.len(), polymer.len() as u128);
+        for (i, element) in polymer.iter().enumerate() {
+            assert_eq!(expansion.element_at(i as u128), Some(*element));
+        }
+        assert_eq!(expansion.element_at(polymer.len() as u128), None);
+
+        for range in [
+            0..polymer.len(),
+            5..1234,
+            777..778,
+            100..100,
+            1000..polymer.len(),
+        ] {
+            let mut expected = HashMap::new();
+            for element in &polymer[range.clone()] {
+                *expected.entry(*element).or_insert(0) += 1;
+            }
+            let counts = expansion
+                .counts_in(range.start as u128..range.end as u128)
+                .unwrap();
+            counts.iter().for_each(|(element, count)| {
+                assert_eq!(expected.get(element).copied().unwrap_or(0), *count)
+            });
+        }
+        assert_eq!(expansion.counts_in(0..polymer.len() as u128 + 1), None);
+
+        // Far too long to be built, but its ends can still be queried
+        let manual = Manual::new(CONTENT).unwrap();
+        let expansion = manual.expansion(40).unwrap();
+        let mut polymer = manual.pair_polymer().unwrap();
+        for _ in 1..=40 {
+            polymer.step().unwrap();
+        }
+        let mut counts = expansion.counts_in(0..expansion.len()).unwrap();
+        counts.retain(|_, count| *count > 0);
+        assert_eq!(counts, polymer.frequencies());
+        assert_eq!(expansion.element_at(0), Some('N'));
+        assert_eq!(expansion.element_at(expansion.len() - 1), Some('B'));
+    }
+
+    #[test]
+    fn it_can_query_the_polymer_after_a_billion_steps() {
+        let manual = Manual::new(CONTENT).unwrap();
+        let steps = 1_000_000_000;
+        let before = manual.expansion(steps - 1).unwrap();
+        let expansion = manual.expansion(steps).unwrap();
+        assert_eq!(expansion.len(), u128::MAX);
+
+        // A step on the start of the polymer gives the start of the polymer after the next step
+        let mut start = manual.clone();
+        start.template = (0..500).map(|i| before.element_at(i).unwrap()).collect();
+        start.step();
+        let start: Vec<char> = start.template.chars().take(999).collect();
+        for (i, element) in start.iter().enumerate() {
+            assert_eq!(expansion.element_at(i as u128), Some(*element));
+        }
+
+        let mut expected = HashMap::new();
+        for element in &start[100..900] {
+            *expected.entry(*element).or_insert(0) += 1;
+        }
+        let mut counts = expansion.counts_in(100..900).unwrap();
+        counts.retain(|_, count| *count > 0);
+        assert_eq!(counts, expected);
+    }
+}
@@ -1,98 +1,18 @@
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::Read;
-use std::path::Path;
-use std::slice::SliceIndex;
+use std::env;
 
-type PolymerTemplate = String;
-type Rules = HashMap<(char, char), char>;
-
-#[derive(Debug, PartialEq, Clone)]
-struct Manual {
-    template: PolymerTemplate,
-    rules: Rules,
-}
-
-impl Manual {
-    fn new(content: String) -> Self {
-        let mut rules = Rules::new();
-
-        let mut split = content.split("\n\n");
-        let template = split.next().unwrap().to_string();
-        let rules_str = split.next().unwrap();
-
-        for rule in rules_str.lines() {
-            let mut iter = rule.split(" -> ");
-            let src: String = iter.next().unwrap().to_string().parse().unwrap();
-            let dest = iter.next().unwrap().to_string().parse().unwrap();
-            rules.insert(
-                (src.chars().nth(0).unwrap(), src.chars().nth(1).unwrap()),
-                dest,
-            );
-        }
-
-        Manual { template, rules }
-    }
-
-    fn step(&mut self) {
-        let mut insertions = HashMap::new();
-        for i in 0..self.template.len() - 1 {
-            let first = self.template[i..i + 1].chars().nth(0).unwrap();
-            let second = self.template[i + 1..i + 2].chars().nth(0).unwrap_or('*');
-            if let Some(dest) = self.rules.get(&(first, second)) {
-                // println!("match found {} {} => {:?}", first, second, dest);
-                // We keep the place and element that should be inserted
-                insertions.insert(i + 1, dest);
-            }
-        }
-
-        // insertions has positions & elemnts that should be inserted
-        // println!("insertions {:?}", insertions);
-        let capa = self.template.len() + insertions.len();
-        let mut new_template = String::with_capacity(capa);
-        self.template.chars().enumerate().for_each(|(i, c)| {
-            if insertions.contains_key(&i) {
-                new_template.push(**insertions.get(&i).unwrap());
-            }
-            new_template.push(c);
-        });
-        self.template = new_template;
-    }
-
-    fn frequencies(&self) -> HashMap<char, u32> {
-        let mut freq = HashMap::new();
-        for ch in self.template.chars() {
-            let counter = freq.entry(ch).or_insert(0);
-            *counter += 1;
-        }
-        freq
-    }
-}
+use common::{input, Solver};
+use day14::{Day14, Manual, Modulo};
 
 fn main() {
-    // Create a path to the desired file
-    let path = Path::new("input.txt");
-    let display = path.display();
-
-    // Open the path in read-only mode, returns `io::Result<File>`
-    let mut file = match File::open(&path) {
-        Err(why) => panic!("couldn't open {}: {}", display, why),
-        Ok(file) => file,
-    };
-
-    // Read the file contents into a string, returns `io::Result<usize>`
-    let mut content = String::new();
-    if let Err(why) = file.read_to_string(&mut content) {
-        panic!("couldn't read {}: {}", display, why)
-    }
+    let content = input::load_or_exit(Day14::DAY, env::args().nth(1).as_deref());
 
     // Part 1
-    let mut manual: Manual = Manual::new(content);
-    // println!("Part1 solution is {:?}", manual);
+    let mut manual: Manual = Manual::new(&content).unwrap_or_else(input::exit);
+    let polymer = manual.pair_polymer();
+    let matrix = manual.pair_matrix();
     for i in 1..=10 {
         manual.step();
         println!("after step {:?}", i);
-        // println!("after step {} template is {:?} ", i, manual.template);
     }
 
     let freq = manual.frequencies();
@@ -101,4 +21,42 @@ fn main() {
     println!("max is {:?}, min is {:?}", max, min);
 
     println!("Part1 result is {:?}", *max - *min);
+
+    // Part 2
+    let Some(mut polymer) = polymer else {
+        println!("Part2 needs pair insertion rules only");
+        return;
+    };
+    for _ in 1..=40 {
+        polymer.step().unwrap_or_else(input::exit);
+    }
+
+    let freq = polymer.frequencies();
+    let max = freq.values().max().unwrap();
+    let min = freq.values().min().unwrap();
+    println!("max is {:?}, min is {:?}", max, min);
+
+    println!("Part2 result is {:?}", *max - *min);
+
+    // Far beyond, through the pair transition matrix
+    let Some(matrix) = matrix else {
+        return;
+    };
+    let mut freq: Vec<(char, Modulo<1_000_000_007>)> =
+        matrix.frequencies(1_000_000_000_000).into_iter().collect();
+    freq.sort();
+    println!("after 10^12 steps, modulo 1000000007 :");
+    for (element, count) in freq {
+        println!("  {} : {}", element, count);
+    }
+
+    println!(
+        "in the long run the polymer grows {:.3} times longer at each step, its elements are :",
+        matrix.growth_rate()
+    );
+    let mut proportions: Vec<(char, f64)> = matrix.limiting_proportions().into_iter().collect();
+    proportions.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (element, share) in proportions {
+        println!("  {} : {:.4}%", element, share * 100.0);
+    }
 }
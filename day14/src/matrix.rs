@@ -0,0 +1,220 @@
+//! Pair counting through the pair transition matrix, for step counts far beyond what stepping allows.
+//!
+//! One step multiplies the vector of pair counts by the transition matrix, so `n` steps are a single
+//! multiplication by the matrix to the power `n`, which fast exponentiation computes in `log2(n)` squarings.
+//! The counts double at each step : after 10^12 steps they only fit modulo a prime, exact counts with
+//! `BigUint` stay practical up to a few hundred steps.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+use crate::{produced_pairs, Manual};
+
+/// What the matrix can count with, such as `u128`, `Modulo<P>` or `BigUint`
+pub trait Count: Clone + From<u64> + Add<Output = Self> + Mul<Output = Self> {}
+
+impl<T> Count for T where T: Clone + From<u64> + Add<Output = T> + Mul<Output = T> {}
+
+/// A count modulo the prime `P`, which must be below 2^63 so that a sum never overflows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Modulo<const P: u64>(u64);
+
+impl<const P: u64> From<u64> for Modulo<P> {
+    fn from(n: u64) -> Self {
+        Modulo(n % P)
+    }
+}
+
+impl<const P: u64> Add for Modulo<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Modulo((self.0 + other.0) % P)
+    }
+}
+
+impl<const P: u64> Mul for Modulo<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Modulo((self.0 as u128 * other.0 as u128 % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> fmt::Display for Modulo<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Upper bound of the power iterations looking for the dominant eigenvector
+const MAX_ITERATIONS: usize = 100_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairMatrix {
+    /// Every pair that can appear in the polymer, from the template on
+    pairs: Vec<(char, char)>,
+    /// `transitions[i][j]` is how many pairs `j` a pair `i` turns into after one step
+    transitions: Vec<Vec<u64>>,
+    /// How many times each pair appears in the template
+    initial: Vec<u64>,
+    /// The last element never changes, it is the only one that starts no pair
+    last: char,
+}
+
+impl Manual {
+    /// The transition matrix of the pairs, only possible when every rule is a pair insertion rule
+    pub fn pair_matrix(&self) -> Option<PairMatrix> {
+        let rules = self.pair_rules()?;
+        let elements: Vec<char> = self.template.chars().collect();
+
+        let pairs = self.reachable_pairs(&rules);
+        let index: HashMap<(char, char), usize> = pairs
+            .iter()
+            .enumerate()
+            .map(|(i, pair)| (*pair, i))
+            .collect();
+
+        let mut transitions = vec![vec![0; pairs.len()]; pairs.len()];
+        for (i, pair) in pairs.iter().enumerate() {
+            for produced in produced_pairs(&rules, *pair) {
+                transitions[i][index[&produced]] += 1;
+            }
+        }
+
+        let mut initial = vec![0; pairs.len()];
+        for pair in elements.windows(2) {
+            initial[index[&(pair[0], pair[1])]] += 1;
+        }
+
+        Some(PairMatrix {
+            pairs,
+            transitions,
+            initial,
+            last: elements[elements.len() - 1],
+        })
+    }
+}
+
+impl PairMatrix {
+    /// How many times each element appears after `steps` steps, counted with `T`.
+    /// Every element that can appear is listed, with a zero count when it is missing at that step.
+    pub fn frequencies<T: Count>(&self, steps: u64) -> HashMap<char, T> {
+        let mut counts: Vec<T> = self.initial.iter().map(|n| T::from(*n)).collect();
+        let mut power: Vec<Vec<T>> = self
+            .transitions
+            .iter()
+            .map(|row| row.iter().map(|n| T::from(*n)).collect())
+            .collect();
+
+        // Square & multiply : `power` is the matrix to the power 2^k, used when bit k of `steps` is set
+        let mut remaining = steps;
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                counts = multiply(&counts, &power);
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                power = power.iter().map(|row| multiply(row, &power)).collect();
+            }
+        }
+
+        // Every element starts exactly one pair, except for the last one
+        let mut freq: HashMap<char, T> = HashMap::from([(self.last, T::from(1))]);
+        for ((first, second), count) in self.pairs.iter().zip(counts) {
+            freq.entry(*second).or_insert_with(|| T::from(0));
+            let total = freq.remove(first).unwrap_or_else(|| T::from(0));
+            freq.insert(*first, total + count);
+        }
+        freq
+    }
+
+    /// How many times longer the polymer gets at each step in the long run : the dominant eigenvalue of
+    /// the transitions reachable from the template
+    pub fn growth_rate(&self) -> f64 {
+        let Some(pairs) = self.dominant_pairs() else {
+            return 1.0;
+        };
+        let transitions = self.float_transitions();
+        multiply(&pairs, &transitions).iter().sum()
+    }
+
+    /// The share of each element in the polymer as the number of steps grows
+    pub fn limiting_proportions(&self) -> HashMap<char, f64> {
+        let Some(pairs) = self.dominant_pairs() else {
+            // A single element template never changes
+            return HashMap::from([(self.last, 1.0)]);
+        };
+
+        // Every element starts one pair, the last element weighs nothing in the long run
+        let mut proportions = HashMap::new();
+        for ((first, second), share) in self.pairs.iter().zip(pairs) {
+            proportions.entry(*second).or_insert(0.0);
+            *proportions.entry(*first).or_insert(0.0) += share;
+        }
+        proportions
+    }
+
+    /// The shares of the pairs once the polymer has grown long enough, `None` for a template without pairs.
+    /// It is found by power iteration on `M + I` rather than `M` : both have the same eigenvectors but
+    /// the former can't oscillate between several of them.
+    fn dominant_pairs(&self) -> Option<Vec<f64>> {
+        let total: u64 = self.initial.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let transitions = self.float_transitions();
+
+        let mut shares: Vec<f64> = self
+            .initial
+            .iter()
+            .map(|n| *n as f64 / total as f64)
+            .collect();
+        for _ in 0..MAX_ITERATIONS {
+            let mut next = multiply(&shares, &transitions);
+            next.iter_mut()
+                .zip(&shares)
+                .for_each(|(n, share)| *n += share);
+            let sum: f64 = next.iter().sum();
+            next.iter_mut().for_each(|n| *n /= sum);
+
+            let change = next
+                .iter()
+                .zip(&shares)
+                .map(|(n, share)| (n - share).abs())
+                .fold(0.0, f64::max);
+            shares = next;
+            if change < 1e-12 {
+                break;
+            }
+        }
+        Some(shares)
+    }
+
+    fn float_transitions(&self) -> Vec<Vec<f64>> {
+        self.transitions
+            .iter()
+            .map(|row| row.iter().map(|n| *n as f64).collect())
+            .collect()
+    }
+}
+
+/// The row vector `vector` multiplied by `matrix`
+fn multiply<T>(vector: &[T], matrix: &[Vec<T>]) -> Vec<T>
+where
+    T: Clone + Add<Output = T> + Mul<Output = T>,
+{
+    let columns = matrix.first().map_or(0, Vec::len);
+    let mut product: Vec<Option<T>> = vec![None; columns];
+    for (n, row) in vector.iter().zip(matrix) {
+        for (total, m) in product.iter_mut().zip(row) {
+            let term = n.clone() * m.clone();
+            *total = Some(match total.take() {
+                Some(total) => total + term,
+                None => term,
+            });
+        }
+    }
+    product.into_iter().flatten().collect()
+}
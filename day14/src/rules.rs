@@ -0,0 +1,123 @@
+//! The rewriting rules of a manual.
+//!
+//! A rule inserts elements in every gap of the polymer that is surrounded by the right contexts, which
+//! makes the `AB -> C` pair insertion of the puzzle one special case of a small L-system :
+//! - `AB -> C` inserts `C` between `A` & `B`, it is the same as `A|B -> C`,
+//! - `CA|B -> XY` inserts `XY` between `CA` & `B`, contexts & insertions can have any length,
+//! - `|B -> X` inserts `X` before every `B`, one context may be empty.
+//!
+//! Every gap of a step is rewritten at once, from the polymer as it was before the step.
+
+use std::cmp::Reverse;
+
+use common::{parse, Result};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rule {
+    /// Elements that must be just before the gap
+    left: Vec<char>,
+    /// Elements that must be just after the gap
+    right: Vec<char>,
+    insertion: Vec<char>,
+}
+
+impl Rule {
+    /// Parses the rule on `line` of `content`, such as `CH -> B` or `CA|B -> XY`
+    pub fn parse(content: &str, line: &str) -> Result<Self> {
+        let mut iter = line.split(" -> ");
+        let pattern = parse::field(content, line, iter.next(), "a pattern")?;
+        let insertion = parse::field(content, line, iter.next(), "` -> ` & the inserted elements")?;
+
+        let (left, right) = match pattern.split_once('|') {
+            Some((left, right)) => (left, right),
+            // Without `|`, the pattern is a pair & the gap is between both elements
+            None => match pattern.char_indices().nth(1) {
+                Some((i, _)) if pattern.chars().count() == 2 => pattern.split_at(i),
+                _ => {
+                    return Err(parse::error(
+                        content,
+                        pattern,
+                        "a pair of elements, or contexts around a `|`",
+                    ))
+                }
+            },
+        };
+        if left.is_empty() && right.is_empty() {
+            return Err(parse::error(content, pattern, "at least one context"));
+        }
+        if insertion.is_empty() || insertion.contains(char::is_whitespace) {
+            return Err(parse::error(content, insertion, "the inserted elements"));
+        }
+
+        Ok(Rule {
+            left: left.chars().collect(),
+            right: right.chars().collect(),
+            insertion: insertion.chars().collect(),
+        })
+    }
+
+    /// Whether the rule applies to the gap before `polymer[gap]`
+    fn matches(&self, polymer: &[char], gap: usize) -> bool {
+        gap >= self.left.len()
+            && polymer[gap - self.left.len()..gap] == self.left[..]
+            && polymer[gap..].starts_with(&self.right)
+    }
+
+    /// How many elements the rule looks at : the more, the more specific
+    fn specificity(&self) -> usize {
+        self.left.len() + self.right.len()
+    }
+
+    /// The pair of a `AB -> ...` pair insertion rule, `None` for the other rules
+    pub fn pair(&self) -> Option<(char, char)> {
+        match (&self.left[..], &self.right[..]) {
+            (&[first], &[second]) => Some((first, second)),
+            _ => None,
+        }
+    }
+
+    pub fn insertion(&self) -> &[char] {
+        &self.insertion
+    }
+}
+
+/// The rules of a manual, by priority : when several rules apply to a gap, the most specific one wins
+/// & the last declared one breaks the ties, so that a rule can be overridden further down the manual.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Rules {
+    by_priority: Vec<Rule>,
+}
+
+impl Rules {
+    /// `rules` in the order they are declared
+    pub fn new(mut rules: Vec<Rule>) -> Self {
+        // The sort is stable : reversing first puts the last declared rules first among equals
+        rules.reverse();
+        rules.sort_by_key(|rule| Reverse(rule.specificity()));
+        Rules { by_priority: rules }
+    }
+
+    /// The rules, from the highest priority to the lowest
+    pub fn iter(&self) -> impl Iterator<Item = &Rule> {
+        self.by_priority.iter()
+    }
+
+    /// The rule applying to the gap before `polymer[gap]`, if any
+    pub fn find(&self, polymer: &[char], gap: usize) -> Option<&Rule> {
+        self.iter().find(|rule| rule.matches(polymer, gap))
+    }
+
+    /// One step : every gap of `polymer` gets the insertion of its rule
+    pub fn rewrite(&self, polymer: &[char]) -> Vec<char> {
+        let mut rewritten = Vec::with_capacity(polymer.len() * 2);
+        for gap in 0..=polymer.len() {
+            if let Some(rule) = self.find(polymer, gap) {
+                rewritten.extend_from_slice(&rule.insertion);
+            }
+            if let Some(&element) = polymer.get(gap) {
+                rewritten.push(element);
+            }
+        }
+        rewritten
+    }
+}
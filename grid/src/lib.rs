@@ -0,0 +1,259 @@
+//! A dense, row-major 2D grid shared by the days working on maps, boards & sheets.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use common::{parse, Result};
+
+/// A cell position : `x` is the column & `y` the row, both starting at 0 in the top left corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Point { x, y }
+    }
+
+    /// The point moved by `(dx, dy)`, `None` when it would go left of or above the grid
+    fn offset(self, (dx, dy): (isize, isize)) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_add_signed(dx)?,
+            self.y.checked_add_signed(dy)?,
+        ))
+    }
+}
+
+/// Offsets of the up, left, right & down neighbours, in reading order
+const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the neighbours including the diagonal ones, in reading order
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` x `height` grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if self.contains(point) {
+            Some(&self.cells[point.y * self.width + point.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.y * self.width + point.x])
+        } else {
+            None
+        }
+    }
+
+    /// Every point of the grid, row after row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| Point::new(i % width, i / width))
+    }
+
+    /// Every cell with its point, row after row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Every cell, mutable, row after row
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// The up, left, right & down neighbours of `point` that are inside the grid
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &NEIGHBOURS_4)
+    }
+
+    /// The neighbours of `point` that are inside the grid, diagonal ones included
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &NEIGHBOURS_8)
+    }
+
+    fn neighbours(
+        &self,
+        point: Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + '_ {
+        offsets
+            .iter()
+            .filter_map(move |&offset| point.offset(offset))
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// The cells of row `y`
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max` keeps `chunks` happy with an empty grid
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of column `x`, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// A grid of the same size with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a map of digits such as `2199943210`, one row per line. Every row must have the same width.
+    pub fn parse_digits(content: &str) -> Result<Self> {
+        let width = content.split('\n').next().unwrap_or("").chars().count();
+        let mut cells = Vec::with_capacity(content.len());
+        let mut height = 0;
+
+        for line in content.split('\n') {
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                let digit = &line[i..i + c.len_utf8()];
+                if x == width {
+                    return Err(parse::error(content, digit, "the end of the row"));
+                }
+                let digit = c
+                    .to_digit(10)
+                    .ok_or_else(|| parse::error(content, digit, "a digit"))?;
+                cells.push(digit as u8);
+            }
+            if line.chars().count() < width {
+                let end_of_line = &line[line.len()..];
+                return Err(parse::error(
+                    content,
+                    end_of_line,
+                    format!("{} digits per row", width),
+                ));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is out of the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is out of the grid", point))
+    }
+}
+
+/// Renders the cells next to each other, one row per line
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grid, Point};
+
+    const CONTENT: &str = "2199943210\n3987894921\n9856789892";
+
+    #[test]
+    fn it_can_parse_digits() {
+        let grid = Grid::parse_digits(CONTENT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (10, 3));
+        assert_eq!(grid[Point::new(1, 0)], 1);
+        assert_eq!(grid.row(2), [9, 8, 5, 6, 7, 8, 9, 8, 9, 2]);
+        assert_eq!(grid.column(9).copied().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(grid.to_string(), CONTENT);
+
+        assert!(Grid::parse_digits("219\n39").is_err());
+        assert!(Grid::parse_digits("219\n3987").is_err());
+        assert!(Grid::parse_digits("219\n3x8").is_err());
+    }
+
+    #[test]
+    fn it_only_iterates_neighbours_inside_the_grid() {
+        let grid = Grid::new(3, 2, 0);
+
+        assert_eq!(
+            grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8(Point::new(2, 1)).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(2, 0), Point::new(1, 1)]
+        );
+        assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 5);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+    }
+}